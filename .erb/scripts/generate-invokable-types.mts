@@ -0,0 +1,216 @@
+// Generate TypeScript types from the JSON Schemas of the Rust invokables and their errors
+//
+// With `--check` this fails instead of writing if the types are out of date.
+import { execSync } from 'node:child_process';
+import fs from 'node:fs';
+import path from 'node:path';
+import webpackPaths from '../configs/webpack.paths.mts';
+
+const schemasCmd =
+  'cargo run --release --quiet --manifest-path=./src-rust/Cargo.toml -p stracciatella-toolset-core -- schemas';
+const outputPath = path.join(
+  webpackPaths.srcPath,
+  'common',
+  'invokables',
+  'generated.ts',
+);
+const check = process.argv.includes('--check');
+
+const { invokables, invokeError } = JSON.parse(
+  execSync(schemasCmd, {
+    cwd: webpackPaths.rootPath,
+    stdio: ['ignore', 'pipe', 'inherit'],
+    maxBuffer: 64 * 1024 * 1024,
+  }).toString(),
+);
+
+const IDENTIFIER = /^[A-Za-z_$][A-Za-z0-9_$]*$/;
+
+function pascalCase(name) {
+  return name
+    .split(/[^A-Za-z0-9]/)
+    .filter((part) => part.length > 0)
+    .map((part) => part[0].toUpperCase() + part.slice(1))
+    .join('');
+}
+
+function literal(value) {
+  if (typeof value !== 'string') {
+    return JSON.stringify(value);
+  }
+  return `'${value.replaceAll('\\', '\\\\').replaceAll("'", "\\'")}'`;
+}
+
+function docComment(description, indent) {
+  if (!description) {
+    return '';
+  }
+  const lines = description.replaceAll('*/', '*\\/').split('\n');
+  if (lines.length === 1) {
+    return `${indent}/** ${lines[0]} */\n`;
+  }
+  const body = lines
+    .map((line) => (line ? `${indent} * ${line}` : `${indent} *`))
+    .join('\n');
+  return `${indent}/**\n${body}\n${indent} */\n`;
+}
+
+function union(types) {
+  const unique = [...new Set(types)];
+  return unique.length > 1
+    ? unique.map((t) => (t.includes(' & ') ? `(${t})` : t)).join(' | ')
+    : unique[0];
+}
+
+function objectType(schema, indent) {
+  const parts = [];
+  const properties = Object.entries(schema.properties ?? {});
+  if (properties.length > 0) {
+    const required = new Set(schema.required ?? []);
+    const inner = `${indent}  `;
+    const fields = properties.map(([key, property]) => {
+      const name = IDENTIFIER.test(key) ? key : literal(key);
+      const optional = required.has(key) ? '' : '?';
+      return `${docComment(property.description, inner)}${inner}${name}${optional}: ${toTs(property, inner)};\n`;
+    });
+    parts.push(`{\n${fields.join('')}${indent}}`);
+  }
+  if (schema.additionalProperties !== undefined) {
+    if (schema.additionalProperties !== false) {
+      parts.push(
+        `Record<string, ${toTs(schema.additionalProperties, indent)}>`,
+      );
+    }
+  } else if (properties.length === 0) {
+    parts.push('Record<string, unknown>');
+  }
+  return parts.length > 0 ? parts.join(' & ') : 'Record<string, never>';
+}
+
+function arrayType(schema, indent) {
+  if (Array.isArray(schema.items)) {
+    return `[${schema.items.map((item) => toTs(item, indent)).join(', ')}]`;
+  }
+  const item =
+    schema.items === undefined ? 'unknown' : toTs(schema.items, indent);
+  return IDENTIFIER.test(item) ? `${item}[]` : `Array<${item}>`;
+}
+
+function primitiveType(type, schema, indent) {
+  switch (type) {
+    case 'string':
+      return 'string';
+    case 'number':
+    case 'integer':
+      return 'number';
+    case 'boolean':
+      return 'boolean';
+    case 'null':
+      return 'null';
+    case 'array':
+      return arrayType(schema, indent);
+    case 'object':
+      return objectType(schema, indent);
+    default:
+      throw new Error(`unsupported JSON Schema type ${type}`);
+  }
+}
+
+function toTs(schema, indent) {
+  if (schema === true) {
+    return 'unknown';
+  }
+  if (schema === false) {
+    return 'never';
+  }
+  if (schema.$ref) {
+    return schema.$ref.split('/').pop();
+  }
+  const parts = [];
+  if (schema.enum) {
+    parts.push(union(schema.enum.map((value) => literal(value))));
+  } else if (schema.const !== undefined) {
+    parts.push(literal(schema.const));
+  } else if (schema.type) {
+    const types = Array.isArray(schema.type) ? schema.type : [schema.type];
+    parts.push(union(types.map((t) => primitiveType(t, schema, indent))));
+  }
+  for (const subschema of schema.allOf ?? []) {
+    parts.push(toTs(subschema, indent));
+  }
+  const variants = schema.oneOf ?? schema.anyOf;
+  if (variants) {
+    parts.push(union(variants.map((variant) => toTs(variant, indent))));
+  }
+  if (parts.length === 0) {
+    return 'unknown';
+  }
+  return parts.length > 1
+    ? parts
+        .map((part) => (part.includes(' | ') ? `(${part})` : part))
+        .join(' & ')
+    : parts[0];
+}
+
+const definitions = new Map();
+const declarations = [];
+
+function declare(name, schema) {
+  declarations.push(
+    `${docComment(schema.description, '')}export type ${name} = ${toTs(schema, '')};\n`,
+  );
+}
+
+function collectDefinitions(invokable, schema) {
+  for (const [name, definition] of Object.entries(schema.definitions ?? {})) {
+    const existing = definitions.get(name);
+    if (
+      existing !== undefined &&
+      JSON.stringify(existing) !== JSON.stringify(definition)
+    ) {
+      throw new Error(
+        `type ${name} of invokable ${invokable} differs from another type with the same name`,
+      );
+    }
+    definitions.set(name, definition);
+  }
+}
+
+const entries = [];
+for (const { name, inputSchema, outputSchema } of invokables) {
+  const typeName = pascalCase(name);
+  collectDefinitions(name, inputSchema);
+  collectDefinitions(name, outputSchema);
+  declare(`${typeName}Input`, inputSchema);
+  declare(`${typeName}Output`, outputSchema);
+  entries.push(
+    `  ${literal(name)}: {\n    input: ${typeName}Input;\n    output: ${typeName}Output;\n  };\n`,
+  );
+}
+collectDefinitions('InvokeError', invokeError);
+declare('InvokeError', invokeError);
+for (const name of [...definitions.keys()].sort()) {
+  declare(name, definitions.get(name));
+}
+
+const output = `// Generated by .erb/scripts/generate-invokable-types.mts from the Rust invokables, do not edit
+
+${declarations.join('\n')}
+/** Parameters and results of the invokables implemented in Rust by name */
+export type RustInvokables = {
+${entries.join('')}};
+`;
+
+if (check) {
+  const current = fs.existsSync(outputPath)
+    ? fs.readFileSync(outputPath, 'utf8')
+    : '';
+  if (current !== output) {
+    console.error(
+      `${path.relative(webpackPaths.rootPath, outputPath)} is out of date, run "npm run generate:invokable-types"`,
+    );
+    process.exit(1);
+  }
+} else {
+  fs.writeFileSync(outputPath, output);
+}
@@ -14,6 +14,7 @@ export default defineConfig(
     'target/**/*',
     'src-rust/target/**/*',
     '.erb/dll/**/*',
+    'src/common/invokables/generated.ts',
   ]),
   eslint.configs.recommended,
   tsPlugin.configs.recommended,
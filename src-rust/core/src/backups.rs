@@ -0,0 +1,134 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{ErrorCode, ToolsetError};
+use crate::{dirs, files};
+
+/// Number of backups that are kept per file, older backups are removed
+const MAX_BACKUPS_PER_FILE: usize = 20;
+
+fn get_backup_dir(mod_id: &str, file: &str) -> Result<PathBuf> {
+    Ok(dirs::project_dirs()?
+        .data_dir()
+        .join("backups")
+        .join(mod_id)
+        .join(file))
+}
+
+pub fn now_millis() -> Result<u64> {
+    let duration = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("system time is before unix epoch")?;
+    Ok(duration.as_millis() as u64)
+}
+
+/// Stores a snapshot of a mod file as a new backup and returns its timestamp
+///
+/// Only the newest `MAX_BACKUPS_PER_FILE` backups are kept.
+pub fn create<T: Serialize>(mod_id: &str, file: &str, snapshot: &T) -> Result<u64> {
+    let dir = get_backup_dir(mod_id, file).context("failed to get backup dir")?;
+    let contents = serde_json::to_string_pretty(snapshot).context("failed to serialize backup")?;
+    let timestamp = reserve_timestamp(&dir)?;
+
+    let path = dir.join(format!("{}.json", timestamp));
+    if let Err(e) = files::write_atomic(&path, contents) {
+        // Don't leave the empty reserved file behind, it would be listed as a broken backup
+        if let Err(e) = files::remove_file(&path) {
+            log::error!("failed to remove reserved backup file: {:#}", e);
+        }
+        return Err(e).context("failed to write backup");
+    }
+
+    let backups = list(mod_id, file).context("failed to list backups")?;
+    for old in backups.iter().skip(MAX_BACKUPS_PER_FILE) {
+        files::remove_file(dir.join(format!("{}.json", old)))
+            .context("failed to remove old backup")?;
+    }
+
+    Ok(timestamp)
+}
+
+/// Reserves a backup file named by the current time, so the returned timestamp is unique
+///
+/// Backups created within the same millisecond get the following free timestamps.
+fn reserve_timestamp(dir: &Path) -> Result<u64> {
+    fs::create_dir_all(dir).context("failed to create backup dir")?;
+    let mut timestamp = now_millis()?;
+    loop {
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(dir.join(format!("{}.json", timestamp)))
+        {
+            Ok(_) => return Ok(timestamp),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => timestamp += 1,
+            Err(e) => return Err(e).context("failed to reserve backup file"),
+        }
+    }
+}
+
+/// Lists the timestamps of all backups for a mod file, newest first
+pub fn list(mod_id: &str, file: &str) -> Result<Vec<u64>> {
+    let dir = get_backup_dir(mod_id, file).context("failed to get backup dir")?;
+    let mut timestamps = vec![];
+
+    for entry in fs::read_dir(&dir).into_iter().flatten() {
+        let entry = entry.context("failed to read backup dir entry")?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if let Some(timestamp) = file_name
+            .strip_suffix(".json")
+            .and_then(|t| t.parse::<u64>().ok())
+        {
+            timestamps.push(timestamp);
+        }
+    }
+    timestamps.sort_unstable_by(|a, b| b.cmp(a));
+
+    Ok(timestamps)
+}
+
+/// Reads the snapshot stored in a backup
+pub fn read<T: DeserializeOwned>(mod_id: &str, file: &str, timestamp: u64) -> Result<T> {
+    let dir = get_backup_dir(mod_id, file).context("failed to get backup dir")?;
+    let path = dir.join(format!("{}.json", timestamp));
+    if !path.exists() {
+        return Err(ToolsetError::new(
+            ErrorCode::FileNotFound,
+            format!("backup `{}` for `{}` not found", timestamp, file),
+        )
+        .with_file(file)
+        .into());
+    }
+    let contents = fs::read_to_string(&path).context("failed to read backup")?;
+
+    serde_json::from_str(&contents).context("failed to deserialize backup")
+}
+
+fn get_mod_backup_dir(mod_id: &str) -> Result<PathBuf> {
+    Ok(dirs::project_dirs()?
+        .data_dir()
+        .join("backups")
+        .join(mod_id))
+}
+
+/// Moves all backups of a mod to a new mod id
+pub fn rename_mod(from: &str, to: &str) -> Result<()> {
+    let target = get_mod_backup_dir(to)?;
+    if target.exists() {
+        return Err(anyhow!("backups for mod `{}` already exist", to));
+    }
+    move_mod(from, &target)
+}
+
+/// Moves all backups of a mod to a directory outside of the backups, e.g. into the trash
+pub fn move_mod(mod_id: &str, to: &Path) -> Result<()> {
+    let dir = get_mod_backup_dir(mod_id)?;
+    if !dir.exists() {
+        return Ok(());
+    }
+    files::move_dir(dir, to)
+}
@@ -0,0 +1,3 @@
+fn main() {
+    std::process::exit(stracciatella_toolset_core::cli::run(std::env::args()));
+}
@@ -0,0 +1,275 @@
+//! Command line interface running invokables without the Electron frontend
+
+use anyhow::{anyhow, Context, Result};
+use schemars::schema_for;
+use serde_json::{json, Value};
+use simplelog::*;
+use std::path::{Path, PathBuf};
+
+use crate::config::{PartialToolsetConfig, ToolsetConfig};
+use crate::error::InvokeError;
+use crate::invokables::Invokables;
+use crate::l10n::Language;
+use crate::state::{self, AppState, ToolsetState};
+
+const USAGE: &str = "\
+Usage: stracciatella-toolset [OPTIONS] <COMMAND> [ARGS]
+
+Commands:
+  invoke <NAME> [PARAMS]          Run any invokable, PARAMS is JSON and defaults to `null`
+  list                            List the names of all invokables
+  schemas                         Print the JSON Schemas of all invokables and their errors
+  lint                            Check the mod, fails if errors are found
+  package <ZIP> [EXTRA]...        Package the mod into a zip file
+  export <FILE> <CSV>             Export an array-of-objects JSON file as CSV
+
+Options:
+  --config <FILE>                 Toolset config file, defaults to the toolset's own config
+  --home <DIR>                    Override the stracciatella home directory
+  --game-dir <DIR>                Override the vanilla game directory
+  --install-dir <DIR>             Override the stracciatella install directory
+  --language <LANGUAGE>           Override the language, e.g. `german`
+  --mod <ID>                      Mod to open, defaults to the last selected mod
+  -h, --help                      Print this help
+";
+
+#[derive(Debug, Default)]
+struct Options {
+    config: Option<PathBuf>,
+    home: Option<String>,
+    game_dir: Option<String>,
+    install_dir: Option<String>,
+    language: Option<Language>,
+    mod_id: Option<String>,
+    help: bool,
+}
+
+/// Runs the command line interface and returns the process exit code
+pub fn run(args: impl IntoIterator<Item = String>) -> i32 {
+    // Logs go to stderr, so stdout only contains results
+    if let Err(e) = TermLogger::init(
+        LevelFilter::Warn,
+        Config::default(),
+        TerminalMode::Stderr,
+        ColorChoice::Auto,
+    ) {
+        eprintln!("failed to initialize logger: {}", e)
+    }
+
+    match run_command(args.into_iter().skip(1).collect()) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            1
+        }
+    }
+}
+
+fn run_command(args: Vec<String>) -> Result<i32> {
+    let (options, command) = parse_options(args)?;
+    if options.help {
+        print!("{}", USAGE);
+        return Ok(0);
+    }
+    let Some((name, args)) = command.split_first() else {
+        eprint!("{}", USAGE);
+        return Ok(2);
+    };
+    let invokables = Invokables::new();
+
+    match name.as_str() {
+        "list" => {
+            for name in invokables.names() {
+                println!("{}", name);
+            }
+            Ok(0)
+        }
+        "schemas" => {
+            let schemas = json!({
+                "invokables": invokables.describe()?,
+                "invokeError": schema_for!(InvokeError),
+            });
+            println!("{}", serde_json::to_string_pretty(&schemas)?);
+            Ok(0)
+        }
+        "invoke" => {
+            let (func, params) = match args {
+                [func] => (func, Value::Null),
+                [func, params] => (
+                    func,
+                    serde_json::from_str(params).context("failed to parse parameters")?,
+                ),
+                _ => {
+                    return Err(anyhow!(
+                        "expected an invokable name and optional parameters"
+                    ))
+                }
+            };
+            let state = load_state(&options)?;
+            let result = invoke(&invokables, &state, func, params)?;
+            println!("{}", serde_json::to_string_pretty(&result)?);
+            Ok(0)
+        }
+        "lint" => {
+            if !args.is_empty() {
+                return Err(anyhow!("lint takes no arguments"));
+            }
+            let state = load_state(&options)?;
+            let findings = invoke(&invokables, &state, "mod/lint", Value::Null)?;
+            let mut errors = 0;
+            for finding in findings.as_array().into_iter().flatten() {
+                let field = |key: &str| finding.get(key).and_then(|v| v.as_str());
+                let severity = field("severity").unwrap_or_default();
+                if severity == "error" {
+                    errors += 1;
+                }
+                let location = match (field("file"), field("pointer")) {
+                    (Some(file), Some(pointer)) => format!(" {}#{}", file, pointer),
+                    (Some(file), None) => format!(" {}", file),
+                    _ => String::new(),
+                };
+                println!(
+                    "{}[{}]{}: {}",
+                    severity,
+                    field("check").unwrap_or_default(),
+                    location,
+                    field("message").unwrap_or_default()
+                );
+            }
+            Ok(if errors > 0 { 1 } else { 0 })
+        }
+        "package" => {
+            let Some((path, extras)) = args.split_first() else {
+                return Err(anyhow!("expected the path of the zip file"));
+            };
+            let state = load_state(&options)?;
+            let result = invoke(
+                &invokables,
+                &state,
+                "mod/package",
+                json!({ "path": absolute(path)?, "extras": extras }),
+            )?;
+            println!("{}", serde_json::to_string_pretty(&result)?);
+            Ok(0)
+        }
+        "export" => {
+            let [file, path] = args else {
+                return Err(anyhow!("expected a JSON file and the path of the CSV file"));
+            };
+            let state = load_state(&options)?;
+            let result = invoke(
+                &invokables,
+                &state,
+                "json/exportCsv",
+                json!({ "file": file, "path": absolute(path)? }),
+            )?;
+            println!("{}", serde_json::to_string_pretty(&result)?);
+            Ok(0)
+        }
+        other => Err(anyhow!("unknown command `{}`, see --help", other)),
+    }
+}
+
+/// Splits leading options from the command and its arguments
+fn parse_options(args: Vec<String>) -> Result<(Options, Vec<String>)> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            return Ok((options, std::iter::once(arg).chain(args).collect()));
+        }
+        if arg == "-h" || arg == "--help" {
+            options.help = true;
+            continue;
+        }
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| anyhow!("option `{}` needs a value", arg))
+        };
+        match arg.as_str() {
+            "--config" => options.config = Some(PathBuf::from(value()?)),
+            "--home" => options.home = Some(value()?),
+            "--game-dir" => options.game_dir = Some(value()?),
+            "--install-dir" => options.install_dir = Some(value()?),
+            "--language" => {
+                options.language = Some(
+                    serde_json::from_value(Value::String(value()?)).context("unknown language")?,
+                )
+            }
+            "--mod" => options.mod_id = Some(value()?),
+            _ => return Err(anyhow!("unknown option `{}`, see --help", arg)),
+        }
+    }
+
+    Ok((options, vec![]))
+}
+
+/// Creates the app state from the config and overrides and opens the mod
+fn load_state(options: &Options) -> Result<AppState> {
+    let mut config = match &options.config {
+        Some(path) => {
+            let json = std::fs::read_to_string(path).context("failed to read config")?;
+            serde_json::from_str::<PartialToolsetConfig>(&json)
+                .context("failed to deserialize config")?
+        }
+        None => match ToolsetConfig::read() {
+            Ok(config) => config.into(),
+            Err((_, partial)) => partial,
+        },
+    };
+    if let Some(home) = &options.home {
+        config.stracciatella_home = Some(home.clone());
+    }
+    if let Some(game_dir) = &options.game_dir {
+        config.vanilla_game_dir = Some(game_dir.clone());
+    }
+    if let Some(install_dir) = &options.install_dir {
+        config.stracciatella_install_dir = Some(install_dir.clone());
+    }
+    if options.language.is_some() {
+        config.language = options.language;
+    }
+    let config = config.to_full_config().ok_or_else(|| {
+        anyhow!("incomplete config, pass --home, --game-dir and --install-dir or use --config")
+    })?;
+    let mod_id = options
+        .mod_id
+        .clone()
+        .or_else(|| config.last_selected_mod.clone());
+
+    let mut state = ToolsetState::configured(config);
+    if let (
+        Some(mod_id),
+        ToolsetState::Configured {
+            config,
+            mod_manager,
+            opened_mod,
+            ..
+        },
+    ) = (mod_id, &mut state)
+    {
+        *opened_mod = Some(
+            state::OpenedMod::new(config, mod_manager, &mod_id)
+                .with_context(|| format!("failed to open mod `{}`", mod_id))?,
+        );
+    }
+
+    Ok(AppState::new(state))
+}
+
+fn invoke(invokables: &Invokables, state: &AppState, func: &str, params: Value) -> Result<Value> {
+    let payload = json!({ "func": func, "params": params }).to_string();
+    let result = invokables.invoke(state, payload)?;
+    serde_json::from_str(&result).context("failed to parse invokable result")
+}
+
+fn absolute(path: &str) -> Result<PathBuf> {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return Ok(path.to_owned());
+    }
+    Ok(std::env::current_dir()
+        .context("failed to get current directory")?
+        .join(path))
+}
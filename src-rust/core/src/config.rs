@@ -1,17 +1,20 @@
 use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use stracciatella::config::{find_stracciatella_home, EngineOptions};
 
 use crate::dirs::project_dirs;
+use crate::l10n::Language;
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PartialToolsetConfig {
     pub stracciatella_home: Option<String>,
     pub vanilla_game_dir: Option<String>,
     pub stracciatella_install_dir: Option<String>,
     pub last_selected_mod: Option<String>,
+    pub language: Option<Language>,
 }
 
 impl PartialToolsetConfig {
@@ -21,6 +24,7 @@ impl PartialToolsetConfig {
             vanilla_game_dir: self.vanilla_game_dir.as_ref()?.clone(),
             stracciatella_install_dir: self.stracciatella_install_dir.as_ref()?.clone(),
             last_selected_mod: self.last_selected_mod.clone(),
+            language: self.language,
         })
     }
 }
@@ -52,6 +56,8 @@ pub struct ToolsetConfig {
     pub vanilla_game_dir: String,
     pub stracciatella_install_dir: String,
     pub last_selected_mod: Option<String>,
+    /// Language whose overrides are used when reading resources
+    pub language: Option<Language>,
 }
 
 impl ToolsetConfig {
@@ -114,6 +120,7 @@ impl Into<PartialToolsetConfig> for ToolsetConfig {
             vanilla_game_dir: Some(self.vanilla_game_dir),
             stracciatella_install_dir: Some(self.stracciatella_install_dir),
             last_selected_mod: self.last_selected_mod,
+            language: self.language,
         }
     }
 }
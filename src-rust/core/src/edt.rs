@@ -0,0 +1,134 @@
+//! Reading and writing of the game's encrypted text files (`.edt`)
+//!
+//! An EDT file is a sequence of fixed length records. Each record is a zero padded UTF-16LE
+//! string where every code unit is shifted by one.
+
+use anyhow::{anyhow, Result};
+
+/// Decodes all records of a file, `record_length` is given in characters
+pub fn decode(bytes: &[u8], record_length: usize) -> Result<Vec<String>> {
+    if record_length == 0 {
+        return Err(anyhow!("record length must not be zero"));
+    }
+    let record_bytes = record_length * 2;
+    let records = bytes.chunks_exact(record_bytes);
+    if !records.remainder().is_empty() {
+        return Err(anyhow!(
+            "file size of {} bytes is not a multiple of the record size of {} bytes",
+            bytes.len(),
+            record_bytes
+        ));
+    }
+
+    records
+        .enumerate()
+        .map(|(index, record)| {
+            let units: Vec<u16> = record
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .take_while(|u| *u != 0)
+                .map(|u| u - 1)
+                .collect();
+            String::from_utf16(&units).map_err(|e| anyhow!("record {}: {}", index, e))
+        })
+        .collect()
+}
+
+/// Encodes records, failing if any of them does not fit into `record_length` characters
+pub fn encode(records: &[String], record_length: usize) -> Result<Vec<u8>> {
+    if record_length == 0 {
+        return Err(anyhow!("record length must not be zero"));
+    }
+    let mut bytes = Vec::with_capacity(records.len() * record_length * 2);
+
+    for (index, record) in records.iter().enumerate() {
+        let units: Vec<u16> = record.encode_utf16().collect();
+        if units.len() > record_length {
+            return Err(anyhow!(
+                "record {} is {} characters long, but only {} fit",
+                index,
+                units.len(),
+                record_length
+            ));
+        }
+        for unit in &units {
+            let encoded = match unit {
+                0 => return Err(anyhow!("record {} contains a null character", index)),
+                u => u
+                    .checked_add(1)
+                    .ok_or_else(|| anyhow!("record {} contains an unsupported character", index))?,
+            };
+            bytes.extend_from_slice(&encoded.to_le_bytes());
+        }
+        bytes.resize(bytes.len() + (record_length - units.len()) * 2, 0);
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_records() {
+        let records = vec![
+            "Hello".to_owned(),
+            "".to_owned(),
+            "Grüße €".to_owned(),
+            "full".to_owned(),
+        ];
+        let bytes = encode(&records, 8).unwrap();
+
+        assert_eq!(bytes.len(), records.len() * 8 * 2);
+        assert_eq!(decode(&bytes, 8).unwrap(), records);
+    }
+
+    #[test]
+    fn shifts_code_units_and_pads_with_zeros() {
+        let bytes = encode(&["AB".to_owned()], 3).unwrap();
+
+        assert_eq!(bytes, vec![0x42, 0x00, 0x43, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn round_trips_records_filling_the_whole_length() {
+        let records = vec!["abcd".to_owned(), "efgh".to_owned()];
+        let bytes = encode(&records, 4).unwrap();
+
+        assert_eq!(decode(&bytes, 4).unwrap(), records);
+    }
+
+    #[test]
+    fn rejects_over_length_records() {
+        let error = encode(&["ok".to_owned(), "too long".to_owned()], 4).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "record 1 is 8 characters long, but only 4 fit"
+        );
+    }
+
+    #[test]
+    fn counts_length_in_utf16_code_units() {
+        // Characters outside of the basic multilingual plane need two code units
+        assert!(encode(&["😀".to_owned()], 1).is_err());
+        assert!(encode(&["😀".to_owned()], 2).is_ok());
+    }
+
+    #[test]
+    fn rejects_null_characters() {
+        assert!(encode(&["a\0b".to_owned()], 4).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        assert!(decode(&[0x42, 0x00, 0x43], 1).is_err());
+    }
+
+    #[test]
+    fn rejects_zero_record_length() {
+        assert!(decode(&[], 0).is_err());
+        assert!(encode(&[], 0).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+//! Detection and conversion of the text encodings found in game files
+
+use anyhow::{anyhow, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Encoding {
+    Utf8,
+    /// UTF-8 with a leading byte order mark
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    /// Single byte encoding used by the western versions of the game
+    Windows1252,
+}
+
+/// Characters for the bytes 0x80 - 0x9f, the remaining bytes map to the same code point
+///
+/// Bytes that are undefined in Windows-1252 map to the C1 control with the same value,
+/// so decoding never fails and arbitrary bytes round-trip.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20ac}', '\u{81}', '\u{201a}', '\u{192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2c6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8d}', '\u{17d}', '\u{8f}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2dc}', '\u{2122}', '\u{161}', '\u{203a}', '\u{153}', '\u{9d}', '\u{17e}', '\u{178}',
+];
+
+impl Encoding {
+    /// Guesses the encoding of a file from its byte order mark and contents
+    ///
+    /// Pure ASCII content reads the same in UTF-8 and Windows-1252, so it is reported as the
+    /// `expected` encoding, unless that is a UTF-16 encoding which requires a byte order mark.
+    pub fn detect(bytes: &[u8], expected: Encoding) -> Self {
+        if bytes.starts_with(&[0xef, 0xbb, 0xbf]) {
+            Encoding::Utf8Bom
+        } else if bytes.starts_with(&[0xff, 0xfe]) {
+            Encoding::Utf16Le
+        } else if bytes.starts_with(&[0xfe, 0xff]) {
+            Encoding::Utf16Be
+        } else if bytes.is_ascii() && !matches!(expected, Encoding::Utf16Le | Encoding::Utf16Be) {
+            expected
+        } else if std::str::from_utf8(bytes).is_ok() {
+            Encoding::Utf8
+        } else {
+            Encoding::Windows1252
+        }
+    }
+
+    pub fn decode(self, bytes: &[u8]) -> Result<String> {
+        match self {
+            Encoding::Utf8 => String::from_utf8(bytes.to_vec()).map_err(|e| anyhow!("{}", e)),
+            Encoding::Utf8Bom => {
+                Encoding::Utf8.decode(bytes.strip_prefix(&[0xef, 0xbb, 0xbf]).unwrap_or(bytes))
+            }
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                let bytes = bytes
+                    .strip_prefix(if self == Encoding::Utf16Le {
+                        &[0xff, 0xfe]
+                    } else {
+                        &[0xfe, 0xff]
+                    })
+                    .unwrap_or(bytes);
+                if bytes.len() % 2 != 0 {
+                    return Err(anyhow!("odd number of bytes in utf-16 text"));
+                }
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|c| {
+                        if self == Encoding::Utf16Le {
+                            u16::from_le_bytes([c[0], c[1]])
+                        } else {
+                            u16::from_be_bytes([c[0], c[1]])
+                        }
+                    })
+                    .collect();
+                String::from_utf16(&units).map_err(|e| anyhow!("{}", e))
+            }
+            Encoding::Windows1252 => Ok(bytes
+                .iter()
+                .map(|&b| match b {
+                    0x80..=0x9f => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+                    b => b as char,
+                })
+                .collect()),
+        }
+    }
+
+    /// Encodes text, failing for characters the encoding cannot represent
+    pub fn encode(self, text: &str) -> Result<Vec<u8>> {
+        match self {
+            Encoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            Encoding::Utf8Bom => {
+                let mut bytes = vec![0xef, 0xbb, 0xbf];
+                bytes.extend_from_slice(text.as_bytes());
+                Ok(bytes)
+            }
+            Encoding::Utf16Le => {
+                let mut bytes = vec![0xff, 0xfe];
+                bytes.extend(text.encode_utf16().flat_map(|u| u.to_le_bytes()));
+                Ok(bytes)
+            }
+            Encoding::Utf16Be => {
+                let mut bytes = vec![0xfe, 0xff];
+                bytes.extend(text.encode_utf16().flat_map(|u| u.to_be_bytes()));
+                Ok(bytes)
+            }
+            Encoding::Windows1252 => text
+                .chars()
+                .map(|c| {
+                    let byte = match WINDOWS_1252_HIGH.iter().position(|&h| h == c) {
+                        Some(index) => Some(0x80 + index as u8),
+                        None => match c as u32 {
+                            code @ (0..=0x7f | 0xa0..=0xff) => Some(code as u8),
+                            _ => None,
+                        },
+                    };
+                    byte.ok_or_else(|| {
+                        anyhow!("character `{}` cannot be encoded as windows-1252", c)
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Encoding; 5] = [
+        Encoding::Utf8,
+        Encoding::Utf8Bom,
+        Encoding::Utf16Le,
+        Encoding::Utf16Be,
+        Encoding::Windows1252,
+    ];
+
+    #[test]
+    fn round_trips_through_every_encoding() {
+        let text = "Grüße, Señor – 5 €";
+        for encoding in ALL {
+            let bytes = encoding.encode(text).unwrap();
+            assert_eq!(Encoding::detect(&bytes, encoding), encoding);
+            assert_eq!(encoding.decode(&bytes).unwrap(), text, "{:?}", encoding);
+        }
+    }
+
+    #[test]
+    fn detects_byte_order_marks() {
+        for encoding in [Encoding::Utf8Bom, Encoding::Utf16Le, Encoding::Utf16Be] {
+            let bytes = encoding.encode("abc").unwrap();
+            assert_eq!(Encoding::detect(&bytes, Encoding::Windows1252), encoding);
+        }
+    }
+
+    #[test]
+    fn detects_ascii_as_expected_encoding() {
+        let bytes = b"plain text";
+        assert_eq!(
+            Encoding::detect(bytes, Encoding::Windows1252),
+            Encoding::Windows1252
+        );
+        assert_eq!(Encoding::detect(bytes, Encoding::Utf8), Encoding::Utf8);
+        assert_eq!(Encoding::detect(bytes, Encoding::Utf16Le), Encoding::Utf8);
+    }
+
+    #[test]
+    fn detects_non_ascii_content() {
+        let utf8 = "Grüße".as_bytes();
+        assert_eq!(
+            Encoding::detect(utf8, Encoding::Windows1252),
+            Encoding::Utf8
+        );
+        let windows_1252 = b"Gr\xfc\xdfe";
+        assert_eq!(
+            Encoding::detect(windows_1252, Encoding::Utf8),
+            Encoding::Windows1252
+        );
+        assert_eq!(Encoding::Windows1252.decode(windows_1252).unwrap(), "Grüße");
+    }
+
+    #[test]
+    fn round_trips_arbitrary_windows_1252_bytes() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let text = Encoding::Windows1252.decode(&bytes).unwrap();
+        assert_eq!(Encoding::Windows1252.encode(&text).unwrap(), bytes);
+    }
+
+    #[test]
+    fn fails_to_encode_unrepresentable_characters() {
+        assert!(Encoding::Windows1252.encode("日本").is_err());
+    }
+
+    #[test]
+    fn fails_to_decode_invalid_input() {
+        assert!(Encoding::Utf8.decode(&[0xff, 0xfe, 0xfd]).is_err());
+        assert!(Encoding::Utf16Le.decode(&[0xff, 0xfe, 0x41]).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! Errors with machine readable codes, so callers can react to specific failures
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Machine readable kind of a failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorCode {
+    /// No invokable with the requested name exists
+    UnknownInvokable,
+    /// The parameters could not be deserialized or failed validation
+    InvalidParams,
+    /// The toolset has not been configured yet
+    NotConfigured,
+    /// The invokable needs a selected mod
+    NoModSelected,
+    /// A file does not exist in the selected mod or vanilla data
+    FileNotFound,
+    /// No schema is known for a JSON file
+    SchemaNotFound,
+    /// A JSON pointer does not point to a value or schema
+    PointerNotFound,
+    /// Any failure without a more specific code
+    Internal,
+}
+
+/// An error with a code and optional details
+///
+/// Return it as the error or attach it as context to an `anyhow` error, the outermost one in the
+/// chain determines the code of the resulting `InvokeError`.
+#[derive(Debug, Clone)]
+pub struct ToolsetError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub file: Option<String>,
+    pub pointer: Option<String>,
+}
+
+impl ToolsetError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            file: None,
+            pointer: None,
+        }
+    }
+
+    pub fn not_configured() -> Self {
+        Self::new(ErrorCode::NotConfigured, "toolset state not configured")
+    }
+
+    pub fn no_mod_selected() -> Self {
+        Self::new(ErrorCode::NoModSelected, "no mod selected")
+    }
+
+    pub fn schema_not_found(file: &str) -> Self {
+        Self::new(
+            ErrorCode::SchemaNotFound,
+            format!("schema for `{}` not found", file),
+        )
+        .with_file(file)
+    }
+
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    pub fn with_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.pointer = Some(pointer.into());
+        self
+    }
+}
+
+impl fmt::Display for ToolsetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ToolsetError {}
+
+/// Serializable form of a failed invocation
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InvokeError {
+    pub code: ErrorCode,
+    /// Message of the root cause
+    pub message: String,
+    /// Messages of the whole chain, from the outermost context to the root cause
+    pub context: Vec<String>,
+    /// File the failure relates to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// JSON pointer into the file the failure relates to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<String>,
+}
+
+impl From<anyhow::Error> for InvokeError {
+    fn from(e: anyhow::Error) -> Self {
+        let details = e.downcast_ref::<ToolsetError>();
+        Self {
+            code: details.map(|d| d.code).unwrap_or(ErrorCode::Internal),
+            message: e.root_cause().to_string(),
+            context: e.chain().map(|c| c.to_string()).collect(),
+            file: details.and_then(|d| d.file.clone()),
+            pointer: details.and_then(|d| d.pointer.clone()),
+        }
+    }
+}
+
+impl fmt::Display for InvokeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.context.join(": "))
+    }
+}
+
+impl std::error::Error for InvokeError {}
@@ -0,0 +1,171 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Distinguishes temporary files of concurrent writers within the same process
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A file that was written to a temporary location next to its target
+///
+/// The temporary file is moved into place by `commit`. If the staged file is dropped
+/// without being committed, the temporary file is removed again.
+#[derive(Debug)]
+pub struct StagedFile {
+    tmp_path: PathBuf,
+    path: PathBuf,
+    committed: bool,
+}
+
+impl StagedFile {
+    /// Writes `contents` to a temporary file in the same directory as `path`
+    ///
+    /// The temporary file name is unique, so concurrent writers to the same target do not
+    /// clobber each other's staged content.
+    pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        let dir = path
+            .parent()
+            .ok_or_else(|| anyhow!("`{}` has no parent directory", path.display()))?;
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("`{}` has no file name", path.display()))?;
+        let tmp_path = dir.join(format!(
+            ".{}.{}-{}.tmp",
+            file_name.to_string_lossy(),
+            std::process::id(),
+            TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        fs::create_dir_all(dir).context("failed to create parent directory")?;
+        let staged = StagedFile {
+            tmp_path,
+            path,
+            committed: false,
+        };
+        {
+            let mut f = fs::File::create(&staged.tmp_path).context("failed to create temp file")?;
+            f.write_all(contents.as_ref())
+                .context("failed to write temp file")?;
+            f.sync_all().context("failed to sync temp file")?;
+        }
+
+        Ok(staged)
+    }
+
+    /// Moves the temporary file to its target path, replacing any existing file
+    pub fn commit(mut self) -> Result<()> {
+        fs::rename(&self.tmp_path, &self.path).with_context(|| {
+            format!(
+                "failed to move temp file into place at `{}`",
+                self.path.display()
+            )
+        })?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Drop for StagedFile {
+    fn drop(&mut self) {
+        if !self.committed {
+            if let Err(e) = remove_file(&self.tmp_path) {
+                log::warn!(
+                    "failed to remove temp file `{}`: {}",
+                    self.tmp_path.display(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// Writes a file by writing to a temporary file first and renaming it into place
+///
+/// This makes sure that a failed write never leaves a truncated file behind.
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<()> {
+    StagedFile::write(path, contents)?.commit()
+}
+
+/// Removes a file, ignoring the error if it does not exist
+pub fn remove_file<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    if let Err(e) = fs::remove_file(path.as_ref()) {
+        if e.kind() != ErrorKind::NotFound {
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// Recursively lists all files in a directory
+///
+/// The returned paths are relative to `dir`. A missing directory results in an empty list.
+pub fn list_files_recursive<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    let mut result = vec![];
+    let mut pending = vec![PathBuf::new()];
+
+    while let Some(relative_dir) = pending.pop() {
+        for entry in fs::read_dir(dir.join(&relative_dir)).into_iter().flatten() {
+            let entry = entry.context("failed to read dir entry")?;
+            let relative_path = relative_dir.join(entry.file_name());
+            if entry.path().is_dir() {
+                pending.push(relative_path);
+            } else {
+                result.push(relative_path);
+            }
+        }
+    }
+    result.sort();
+
+    Ok(result)
+}
+
+/// Converts a relative path to a resource path using `/` as separator
+pub fn to_resource_path<P: AsRef<Path>>(path: P) -> String {
+    path.as_ref()
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Recursively copies a directory, `to` must not exist yet
+pub fn copy_dir_recursive<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    let mut pending = vec![PathBuf::new()];
+
+    while let Some(relative_dir) = pending.pop() {
+        fs::create_dir(to.join(&relative_dir))
+            .with_context(|| format!("failed to create `{}`", to.join(&relative_dir).display()))?;
+        for entry in fs::read_dir(from.join(&relative_dir)).context("failed to read dir")? {
+            let entry = entry.context("failed to read dir entry")?;
+            let relative_path = relative_dir.join(entry.file_name());
+            if entry.path().is_dir() {
+                pending.push(relative_path);
+            } else {
+                fs::copy(entry.path(), to.join(&relative_path))
+                    .with_context(|| format!("failed to copy `{}`", relative_path.display()))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves a directory, falling back to copying when it cannot be renamed, e.g. across devices
+pub fn move_dir<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).context("failed to create parent dir")?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if let Err(e) = copy_dir_recursive(from, to) {
+        let _ = fs::remove_dir_all(to);
+        return Err(e);
+    }
+    fs::remove_dir_all(from).context("failed to remove source dir")
+}
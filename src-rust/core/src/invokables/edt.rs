@@ -0,0 +1,117 @@
+use crate::{edt, files, invokables::Invokable, l10n, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::io::Read as _;
+
+fn validate_file(file: &str) -> Result<()> {
+    if file.contains("..") {
+        return Err(anyhow!("must not contain `..`"));
+    }
+    if !file.to_lowercase().ends_with(".edt") {
+        return Err(anyhow!("must end with `.edt`"));
+    }
+    Ok(())
+}
+
+fn read_edt(state: &state::ToolsetState, file: &str, record_length: usize) -> Result<EdtFile> {
+    let selected_mod = state
+        .try_selected_mod()
+        .context("failed to get selected mod")?;
+    let localized = l10n::localize(state, file)?;
+    let mut bytes = vec![];
+    state
+        .open_file(&localized)
+        .context("failed to open file")?
+        .read_to_end(&mut bytes)
+        .context("failed to read file")?;
+    let records = edt::decode(&bytes, record_length).context("failed to decode file")?;
+
+    Ok(EdtFile {
+        record_length,
+        records,
+        modified: selected_mod.data_path(&localized).exists(),
+        localized: Some(localized).filter(|l| l != file),
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EdtFile {
+    /// Length of each record in characters
+    record_length: usize,
+    records: Vec<String>,
+    /// Whether the selected mod provides the file
+    modified: bool,
+    /// Language specific file that was read instead of the requested one
+    localized: Option<String>,
+}
+
+/// Reads the records of an EDT file, e.g. `binarydata/itemdesc.edt`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Read {
+    file: String,
+    record_length: usize,
+}
+
+impl Invokable for Read {
+    type Output = EdtFile;
+
+    fn name() -> &'static str {
+        "edt/read"
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_file(&self.file).context("failed to validate filename")?;
+        if self.record_length == 0 {
+            return Err(anyhow!("record length must not be zero"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        read_edt(&state, &self.file, self.record_length)
+    }
+}
+
+/// Writes the records of an EDT file to the selected mod
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Persist {
+    file: String,
+    record_length: usize,
+    records: Vec<String>,
+}
+
+impl Invokable for Persist {
+    type Output = EdtFile;
+
+    fn name() -> &'static str {
+        "edt/persist"
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_file(&self.file).context("failed to validate filename")?;
+        if self.record_length == 0 {
+            return Err(anyhow!("record length must not be zero"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let bytes =
+            edt::encode(&self.records, self.record_length).context("failed to encode records")?;
+
+        let localized = l10n::localize(&state, &self.file)?;
+        files::write_atomic(selected_mod.data_path(&localized), bytes)
+            .context("failed to write file")?;
+
+        read_edt(&state, &self.file, self.record_length).context("failed to get value after update")
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{invokables::Invokable, state::AppState};
 use anyhow::{anyhow, Context, Result};
 use image::RgbaImage;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{Deserialize, Serialize};
 use std::io::BufReader;
 use stracciatella::file_formats::stci::{Stci, StciRgb888};
@@ -40,7 +41,18 @@ impl Serialize for Base64Image {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl JsonSchema for Base64Image {
+    fn schema_name() -> String {
+        "Base64Image".to_owned()
+    }
+
+    // Serialized as a data URL
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Render {
     file: String,
     subimage: Option<usize>,
@@ -136,12 +148,12 @@ impl Invokable for Render {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReadMetadata {
     file: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SubImageMetadata {
     width: u16,
     height: u16,
@@ -149,7 +161,7 @@ pub struct SubImageMetadata {
     offset_y: i16,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ImageFileMetadata {
     images: Vec<SubImageMetadata>,
 }
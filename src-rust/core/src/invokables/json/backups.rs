@@ -0,0 +1,90 @@
+use crate::invokables::Invokable;
+use crate::state;
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{localize, persist, Filename, JsonFileWithSchema, Persisted, Read};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Backup {
+    timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListBackups {
+    file: Filename,
+}
+
+impl Invokable for ListBackups {
+    type Output = Vec<Backup>;
+
+    fn name() -> &'static str {
+        "json/listBackups"
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        // Backups are created for the language specific file that edits are written to
+        let localized = localize(&state, &self.file)?;
+        let timestamps = crate::backups::list(selected_mod.m.id(), localized.as_str())
+            .context("failed to list backups")?;
+
+        Ok(timestamps
+            .into_iter()
+            .map(|timestamp| Backup { timestamp })
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RestoreBackup {
+    file: Filename,
+    timestamp: u64,
+}
+
+impl Invokable for RestoreBackup {
+    type Output = JsonFileWithSchema;
+
+    fn name() -> &'static str {
+        "json/restoreBackup"
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        Ok(())
+    }
+
+    fn invoke(&self, app_state: &state::AppState) -> Result<Self::Output> {
+        {
+            let state = app_state.read();
+            let selected_mod = state
+                .try_selected_mod()
+                .context("failed to get selected mod")?;
+            let localized = localize(&state, &self.file)?;
+            let snapshot: Persisted =
+                crate::backups::read(selected_mod.m.id(), localized.as_str(), self.timestamp)
+                    .context("failed to read backup")?;
+
+            persist(selected_mod, &localized, &snapshot).context("failed to restore backup")?;
+        }
+
+        Read {
+            file: self.file.clone(),
+        }
+        .invoke(app_state)
+        .context("failed to get value after restore")
+    }
+}
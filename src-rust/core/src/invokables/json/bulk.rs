@@ -0,0 +1,198 @@
+use crate::invokables::Invokable;
+use crate::state;
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+use super::search::Predicate;
+use super::{
+    field_value, item_name, localize, persist, read_effective, read_persisted, read_vanilla,
+    set_field, Filename, JsonFileWithSchema, Read, SaveMode,
+};
+
+/// An update of a single field, `field` is a dot separated path into the item
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum Assignment {
+    /// Sets the field to a value, creating it if necessary
+    Set { field: String, value: Value },
+    /// Adds a number to a numeric field
+    Add { field: String, value: f64 },
+    /// Multiplies a numeric field by a factor
+    Multiply { field: String, value: f64 },
+}
+
+impl Assignment {
+    fn field(&self) -> &str {
+        match self {
+            Assignment::Set { field, .. } => field,
+            Assignment::Add { field, .. } => field,
+            Assignment::Multiply { field, .. } => field,
+        }
+    }
+
+    /// Computes the new value of the field from its current value
+    fn evaluate(&self, current: Option<&Value>) -> Result<Value> {
+        let (current, operand, multiply) = match self {
+            Assignment::Set { value, .. } => return Ok(value.clone()),
+            Assignment::Add { value, .. } => (current, *value, false),
+            Assignment::Multiply { value, .. } => (current, *value, true),
+        };
+        let current = current.ok_or_else(|| anyhow!("field does not exist"))?;
+        let number = current
+            .as_f64()
+            .ok_or_else(|| anyhow!("field is not a number"))?;
+        let result = if multiply {
+            number * operand
+        } else {
+            number + operand
+        };
+
+        // Keep integer fields integers, the engine does not accept fractions for those
+        let result = if current.is_i64() || current.is_u64() {
+            Some(result.round())
+                .filter(|n| n.is_finite())
+                .map(|n| Value::from(n as i64))
+        } else {
+            Number::from_f64(result).map(Value::Number)
+        };
+        result.ok_or_else(|| anyhow!("result is not a finite number"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkChange {
+    pointer: String,
+    item_name: Option<String>,
+    field: String,
+    old_value: Option<Value>,
+    new_value: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BulkUpdateResult {
+    changes: Vec<BulkChange>,
+    file: Option<JsonFileWithSchema>,
+}
+
+/// Updates fields of all items of a file matching a filter
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdate {
+    file: Filename,
+    filter: Option<Predicate>,
+    assignments: Vec<Assignment>,
+    save_mode: Option<SaveMode>,
+    dry_run: Option<bool>,
+}
+
+impl Invokable for BulkUpdate {
+    type Output = BulkUpdateResult;
+
+    fn name() -> &'static str {
+        "json/bulkUpdate"
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        if let Some(filter) = &self.filter {
+            filter.validate().context("invalid filter")?;
+        }
+        if self.assignments.is_empty() {
+            return Err(anyhow!("at least one assignment is required"));
+        }
+        if self.assignments.iter().any(|a| a.field().is_empty()) {
+            return Err(anyhow!("assignment field must not be empty"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, app_state: &state::AppState) -> Result<Self::Output> {
+        let dry_run = self.dry_run.unwrap_or(false);
+        let changes = {
+            let state = app_state.read();
+            let selected_mod = state
+                .try_selected_mod()
+                .context("failed to get selected mod")?;
+            let mut value = read_effective(&state, &self.file).context("failed to read json")?;
+            let changes = match &mut value {
+                Value::Array(items) => {
+                    let mut changes = vec![];
+                    for (index, item) in items.iter_mut().enumerate() {
+                        changes.extend(self.update_item(&format!("/{}", index), item)?);
+                    }
+                    changes
+                }
+                item => self.update_item("", item)?,
+            };
+
+            if !dry_run && !changes.is_empty() {
+                let localized = localize(&state, &self.file)?;
+                let persisted = read_persisted(selected_mod, &localized)?;
+                let save_mode = self.save_mode.unwrap_or_else(|| SaveMode::of(&persisted));
+                let vanilla = read_vanilla(selected_mod, &localized)?;
+                let persisted = save_mode.to_persisted(&vanilla, value)?;
+                persist(selected_mod, &localized, &persisted)?;
+            }
+
+            changes
+        };
+
+        let file = if dry_run {
+            None
+        } else {
+            Some(
+                Read {
+                    file: self.file.clone(),
+                }
+                .invoke(app_state)
+                .context("failed to get value after update")?,
+            )
+        };
+
+        Ok(BulkUpdateResult { changes, file })
+    }
+}
+
+impl BulkUpdate {
+    /// Applies all assignments to an item if it matches the filter
+    fn update_item(&self, pointer: &str, item: &mut Value) -> Result<Vec<BulkChange>> {
+        if let Some(filter) = &self.filter {
+            if !filter.matches(item) {
+                return Ok(vec![]);
+            }
+        }
+        let mut changes = vec![];
+        let name = item_name(item);
+
+        for assignment in &self.assignments {
+            let field = assignment.field();
+            let old_value = field_value(item, field).cloned();
+            let new_value = assignment.evaluate(old_value.as_ref()).with_context(|| {
+                format!(
+                    "failed to update `{}` of item `{}`",
+                    field,
+                    name.as_deref().unwrap_or(pointer)
+                )
+            })?;
+            if old_value.as_ref() == Some(&new_value) {
+                continue;
+            }
+            set_field(item, field, new_value.clone())
+                .with_context(|| format!("failed to set `{}`", field))?;
+            changes.push(BulkChange {
+                pointer: pointer.to_owned(),
+                item_name: name.clone(),
+                field: field.to_owned(),
+                old_value,
+                new_value,
+            });
+        }
+
+        Ok(changes)
+    }
+}
@@ -0,0 +1,227 @@
+use crate::invokables::Invokable;
+use crate::state::{self, OpenedMod};
+use crate::{files, json_schema, l10n};
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::Path;
+use stracciatella::schemas::SchemaManager;
+
+use super::migrations::{RegisteredMigration, MIGRATIONS};
+use super::{apply_patch, read_persisted, read_vanilla, Filename};
+
+/// Part of a mod file an issue was found in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueSource {
+    /// The file itself, e.g. it could not be parsed or has no schema
+    File,
+    /// The value replacing vanilla
+    Value,
+    /// The patch, or the document resulting from applying it
+    Patch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompatibilityIssue {
+    pub source: IssueSource,
+    pub pointer: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FileCompatibility {
+    pub file: String,
+    pub issues: Vec<CompatibilityIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PendingMigration {
+    id: String,
+    description: String,
+    files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityReport {
+    files: Vec<FileCompatibility>,
+    pending_migrations: Vec<PendingMigration>,
+}
+
+/// Validates all JSON values and patches of the selected mod against the current schemas
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CheckSchemaCompatibility;
+
+impl Invokable for CheckSchemaCompatibility {
+    type Output = CompatibilityReport;
+
+    fn name() -> &'static str {
+        "json/checkSchemaCompatibility"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let schema_manager = state
+            .try_schema_manager()
+            .context("failed to get schema manager")?;
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+
+        Ok(CompatibilityReport {
+            files: check_mod_json(selected_mod, schema_manager)?,
+            pending_migrations: pending_migrations(selected_mod)?,
+        })
+    }
+}
+
+/// Validates every JSON value and patch the selected mod provides against its schema
+pub fn check_mod_json(
+    selected_mod: &OpenedMod,
+    schema_manager: &SchemaManager,
+) -> Result<Vec<FileCompatibility>> {
+    let mut files = vec![];
+    for file in list_mod_json_files(selected_mod)? {
+        // Language specific variants are validated against the schema of the file they override
+        let issues = match schema_manager.get(Path::new(&l10n::base_resource(&file))) {
+            Some(schema) => check_file(selected_mod, schema.as_value(), &file),
+            None => vec![CompatibilityIssue {
+                source: IssueSource::File,
+                pointer: "".to_owned(),
+                message: "no schema found, the file might have been moved or removed".to_owned(),
+            }],
+        };
+        files.push(FileCompatibility { file, issues });
+    }
+    Ok(files)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MigrationResult {
+    applied: Vec<PendingMigration>,
+}
+
+/// Applies all pending migrations to the selected mod
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Migrate;
+
+impl Invokable for Migrate {
+    type Output = MigrationResult;
+
+    fn name() -> &'static str {
+        "json/migrate"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+
+        let mut applied = vec![];
+        for migration in MIGRATIONS {
+            let files = migration
+                .affected_files(selected_mod)
+                .with_context(|| format!("failed to check migration `{}`", migration.id))?;
+            if files.is_empty() {
+                continue;
+            }
+            migration
+                .apply(selected_mod)
+                .with_context(|| format!("failed to apply migration `{}`", migration.id))?;
+            applied.push(pending_migration(migration, files));
+        }
+
+        Ok(MigrationResult { applied })
+    }
+}
+
+fn pending_migration(migration: &RegisteredMigration, files: Vec<String>) -> PendingMigration {
+    PendingMigration {
+        id: migration.id.to_owned(),
+        description: migration.description.to_owned(),
+        files,
+    }
+}
+
+fn pending_migrations(selected_mod: &OpenedMod) -> Result<Vec<PendingMigration>> {
+    let mut result = vec![];
+    for migration in MIGRATIONS {
+        let files = migration
+            .affected_files(selected_mod)
+            .with_context(|| format!("failed to check migration `{}`", migration.id))?;
+        if !files.is_empty() {
+            result.push(pending_migration(migration, files));
+        }
+    }
+    Ok(result)
+}
+
+/// Lists the JSON documents a mod provides a value or patch for
+fn list_mod_json_files(selected_mod: &OpenedMod) -> Result<Vec<String>> {
+    let data_dir = selected_mod.data_path("");
+    let mut result = BTreeSet::new();
+
+    for path in files::list_files_recursive(&data_dir)
+        .with_context(|| format!("failed to list files in `{}`", data_dir.display()))?
+    {
+        let path = files::to_resource_path(&path);
+        if let Some(base) = path.strip_suffix(".patch.json") {
+            result.insert(format!("{}.json", base));
+        } else if path.ends_with(".json") {
+            result.insert(path);
+        }
+    }
+
+    Ok(result.into_iter().collect())
+}
+
+fn check_file(selected_mod: &OpenedMod, schema: &Value, file: &str) -> Vec<CompatibilityIssue> {
+    let filename = Filename(file.to_owned());
+    let mut issues = vec![];
+    let mut issue = |source, pointer: String, message: String| {
+        issues.push(CompatibilityIssue {
+            source,
+            pointer,
+            message,
+        })
+    };
+
+    let persisted = match read_persisted(selected_mod, &filename) {
+        Ok(persisted) => persisted,
+        Err(e) => {
+            issue(IssueSource::File, "".to_owned(), format!("{:#}", e));
+            return issues;
+        }
+    };
+
+    if let Some(value) = &persisted.value {
+        match json_schema::validate(schema, value) {
+            Ok(found) => found
+                .into_iter()
+                .for_each(|i| issue(IssueSource::Value, i.pointer, i.message)),
+            Err(e) => issue(IssueSource::File, "".to_owned(), format!("{:#}", e)),
+        }
+    }
+    if let Some(patch) = persisted.patch {
+        let base = match persisted.value {
+            Some(value) => Ok(value),
+            None => read_vanilla(selected_mod, &filename),
+        };
+        let patched = base.and_then(|mut base| {
+            apply_patch(&mut base, patch)?;
+            Ok(base)
+        });
+        match patched.and_then(|patched| json_schema::validate(schema, &patched)) {
+            Ok(found) => found
+                .into_iter()
+                .for_each(|i| issue(IssueSource::Patch, i.pointer, i.message)),
+            Err(e) => issue(IssueSource::Patch, "".to_owned(), format!("{:#}", e)),
+        }
+    }
+
+    issues
+}
@@ -0,0 +1,409 @@
+use crate::error::ToolsetError;
+use crate::invokables::Invokable;
+use crate::state;
+use crate::{csv, files, json_schema};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{
+    field_value, localize, persist, read_effective, read_persisted, read_vanilla, set_field,
+    Filename, JsonFileWithSchema, Read, SaveMode,
+};
+
+/// Reads the effective items of a file together with the file's schema
+fn read_items(state: &state::ToolsetState, file: &Filename) -> Result<(Vec<Value>, Value)> {
+    let schema_manager = state
+        .try_schema_manager()
+        .context("failed to get schema manager")?;
+    let schema = schema_manager
+        .get(Path::new(file.as_str()))
+        .ok_or_else(|| ToolsetError::schema_not_found(file.as_str()))?
+        .as_value()
+        .clone();
+    if item_schema(&schema).is_none() {
+        return Err(anyhow!("`{}` is not an array of objects", file.as_str()));
+    }
+
+    let value = read_effective(state, file).context("failed to read json")?;
+    let items = match value {
+        Value::Array(items) if items.iter().all(|i| i.is_object()) => items,
+        _ => return Err(anyhow!("`{}` is not an array of objects", file.as_str())),
+    };
+
+    Ok((items, schema))
+}
+
+fn item_schema(schema: &Value) -> Option<&Value> {
+    json_schema::items(schema, schema).filter(|s| json_schema::types(schema, s).contains(&"object"))
+}
+
+/// Determines the columns for a set of items
+///
+/// Nested objects are flattened to dot separated column names, everything else
+/// (including arrays) is a single column. Columns known from the schema come first.
+fn columns(items: &[Value], schema: &Value) -> Vec<String> {
+    let mut columns = vec![];
+    if let Some(item_schema) = item_schema(schema) {
+        schema_columns(schema, item_schema, "", &mut columns);
+    }
+    let mut extra = vec![];
+    for item in items {
+        value_columns(item, "", &mut extra);
+    }
+    extra.sort();
+    for column in extra {
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    columns
+}
+
+fn join_field(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn schema_columns(root: &Value, schema: &Value, prefix: &str, columns: &mut Vec<String>) {
+    for (key, property) in json_schema::properties(root, schema) {
+        let field = join_field(prefix, key);
+        if json_schema::types(root, property) == ["object"]
+            && !json_schema::properties(root, property).is_empty()
+        {
+            schema_columns(root, property, &field, columns);
+        } else {
+            columns.push(field);
+        }
+    }
+}
+
+fn value_columns(value: &Value, prefix: &str, columns: &mut Vec<String>) {
+    for (key, value) in value.as_object().into_iter().flatten() {
+        let field = join_field(prefix, key);
+        match value {
+            Value::Object(o) if !o.is_empty() => value_columns(value, &field, columns),
+            _ => {
+                if !columns.contains(&field) {
+                    columns.push(field)
+                }
+            }
+        }
+    }
+}
+
+/// Converts a value to a cell
+///
+/// Missing values are empty cells and `null` is written as `null`. Strings are written as they
+/// are, unless they would be read back as something else: empty strings, the string `null` and
+/// strings starting with a quote are written as JSON strings (e.g. `""`).
+fn to_cell(value: Option<&Value>) -> String {
+    match value {
+        None => String::new(),
+        Some(Value::String(s)) if s.is_empty() || s == "null" || s.starts_with('"') => {
+            Value::from(s.as_str()).to_string()
+        }
+        Some(Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CsvExport {
+    columns: Vec<String>,
+    rows: usize,
+}
+
+/// Exports an array-of-objects file (e.g. `weapons.json`) as CSV to a path on the host
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportCsv {
+    file: Filename,
+    path: PathBuf,
+}
+
+impl Invokable for ExportCsv {
+    type Output = CsvExport;
+
+    fn name() -> &'static str {
+        "json/exportCsv"
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        if !self.path.is_absolute() {
+            return Err(anyhow!("export path must be absolute"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let (items, schema) = read_items(&state, &self.file)?;
+        let columns = columns(&items, &schema);
+
+        let mut rows = vec![columns.clone()];
+        for item in &items {
+            rows.push(
+                columns
+                    .iter()
+                    .map(|c| to_cell(field_value(item, c)))
+                    .collect(),
+            );
+        }
+        files::write_atomic(&self.path, csv::write(&rows).as_bytes())
+            .with_context(|| format!("failed to write `{}`", self.path.display()))?;
+
+        Ok(CsvExport {
+            columns,
+            rows: items.len(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CsvCellError {
+    /// Row in the CSV file, the header being row 1
+    row: usize,
+    column: String,
+    value: String,
+    message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CsvImport {
+    errors: Vec<CsvCellError>,
+    file: Option<JsonFileWithSchema>,
+}
+
+/// Imports a CSV file previously created by `json/exportCsv`, replacing all items of the file
+///
+/// Nothing is written if any cell fails to convert to the type required by the schema, or if
+/// the resulting document does not validate against the schema.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportCsv {
+    file: Filename,
+    path: PathBuf,
+    save_mode: Option<SaveMode>,
+}
+
+impl Invokable for ImportCsv {
+    type Output = CsvImport;
+
+    fn name() -> &'static str {
+        "json/importCsv"
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        if !self.path.is_absolute() {
+            return Err(anyhow!("import path must be absolute"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, app_state: &state::AppState) -> Result<Self::Output> {
+        let input = fs::read_to_string(&self.path)
+            .with_context(|| format!("failed to read `{}`", self.path.display()))?;
+        let rows = csv::read(&input).context("failed to parse csv")?;
+        let (header, rows) = rows
+            .split_first()
+            .ok_or_else(|| anyhow!("csv file is empty"))?;
+        if let Some(empty) = header.iter().position(|c| c.is_empty()) {
+            return Err(anyhow!("column {} has no name", empty + 1));
+        }
+
+        {
+            let state = app_state.read();
+            let (_, schema) = read_items(&state, &self.file)?;
+            let item_schema = item_schema(&schema)
+                .ok_or_else(|| anyhow!("`{}` is not an array of objects", self.file.as_str()))?;
+
+            let mut errors = vec![];
+            let mut items = vec![];
+            let mut row_numbers = vec![];
+            for (index, row) in rows.iter().enumerate() {
+                let row_number = index + 2;
+                if row.iter().all(|c| c.is_empty()) {
+                    continue;
+                }
+                if row.len() > header.len() {
+                    errors.push(CsvCellError {
+                        row: row_number,
+                        column: "".to_owned(),
+                        value: row[header.len()..].join(","),
+                        message: "row has more cells than the header".to_owned(),
+                    });
+                    continue;
+                }
+                let mut item = Value::Object(Map::new());
+                for (column, cell) in header.iter().zip(row) {
+                    // Empty cells are missing fields, empty strings are written as `""`
+                    if cell.is_empty() {
+                        continue;
+                    }
+                    let cell_schema = json_schema::field(&schema, item_schema, column);
+                    match coerce(&schema, cell_schema, cell)
+                        .and_then(|value| set_field(&mut item, column, value))
+                    {
+                        Ok(()) => {}
+                        Err(e) => errors.push(CsvCellError {
+                            row: row_number,
+                            column: column.clone(),
+                            value: cell.clone(),
+                            message: format!("{:#}", e),
+                        }),
+                    }
+                }
+                items.push(item);
+                row_numbers.push(row_number);
+            }
+
+            if !errors.is_empty() {
+                return Ok(CsvImport { errors, file: None });
+            }
+
+            let document = Value::Array(items);
+            let issues = json_schema::validate(&schema, &document)
+                .context("failed to validate imported items")?;
+            if !issues.is_empty() {
+                let errors = issues
+                    .into_iter()
+                    .map(|issue| validation_error(header, rows, &row_numbers, issue))
+                    .collect();
+                return Ok(CsvImport { errors, file: None });
+            }
+
+            let selected_mod = state
+                .try_selected_mod()
+                .context("failed to get selected mod")?;
+            let localized = localize(&state, &self.file)?;
+            let persisted = read_persisted(selected_mod, &localized)?;
+            let save_mode = self.save_mode.unwrap_or_else(|| SaveMode::of(&persisted));
+            let vanilla = read_vanilla(selected_mod, &localized)?;
+            let persisted = save_mode.to_persisted(&vanilla, document)?;
+            persist(selected_mod, &localized, &persisted)?;
+        }
+
+        let file = Read {
+            file: self.file.clone(),
+        }
+        .invoke(app_state)
+        .context("failed to get value after import")?;
+        Ok(CsvImport {
+            errors: vec![],
+            file: Some(file),
+        })
+    }
+}
+
+/// Maps a schema violation of the imported document to the cell it originates from
+///
+/// Violations of a whole item (e.g. a missing required field) have no column.
+fn validation_error(
+    header: &[String],
+    rows: &[Vec<String>],
+    row_numbers: &[usize],
+    issue: json_schema::ValidationIssue,
+) -> CsvCellError {
+    let mut tokens = issue
+        .pointer
+        .split('/')
+        .skip(1)
+        .map(|token| token.replace("~1", "/").replace("~0", "~"));
+    let row = tokens
+        .next()
+        .and_then(|index| index.parse::<usize>().ok())
+        .and_then(|index| row_numbers.get(index).copied())
+        .unwrap_or(0);
+    let field: Vec<_> = tokens.collect();
+    // Values inside of arrays and objects stored in a single cell belong to that cell
+    let column = (1..=field.len())
+        .rev()
+        .map(|len| field[..len].join("."))
+        .find(|column| header.contains(column))
+        .unwrap_or_default();
+    let value = header
+        .iter()
+        .position(|c| *c == column)
+        .filter(|_| !column.is_empty())
+        .and_then(|position| rows.get(row.checked_sub(2)?)?.get(position))
+        .cloned()
+        .unwrap_or_default();
+
+    CsvCellError {
+        row,
+        column,
+        value,
+        message: issue.message,
+    }
+}
+
+/// Converts a cell to the type the schema expects
+///
+/// `null` is always converted to null and cells starting with a quote are read as JSON strings,
+/// as written by `to_cell`. Cells without schema are parsed as JSON if possible and kept as
+/// string otherwise.
+fn coerce(root: &Value, schema: Option<&Value>, cell: &str) -> Result<Value> {
+    if cell == "null" {
+        return Ok(Value::Null);
+    }
+    if cell.starts_with('"') {
+        if let Ok(Value::String(s)) = serde_json::from_str(cell) {
+            return Ok(Value::String(s));
+        }
+    }
+    let types = match schema {
+        Some(schema) => json_schema::types(root, schema),
+        None => return Ok(serde_json::from_str(cell).unwrap_or_else(|_| Value::from(cell))),
+    };
+    if types.is_empty() {
+        return Ok(serde_json::from_str(cell).unwrap_or_else(|_| Value::from(cell)));
+    }
+
+    for t in ["integer", "number", "boolean", "array", "object"] {
+        if !types.contains(&t) {
+            continue;
+        }
+        let value = match t {
+            "integer" => cell.trim().parse::<i64>().ok().map(Value::from),
+            "number" => cell
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map(|n| {
+                    if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+                        Value::from(n as i64)
+                    } else {
+                        Value::from(n)
+                    }
+                }),
+            "boolean" => match cell.trim().to_lowercase().as_str() {
+                "true" | "yes" | "1" => Some(Value::Bool(true)),
+                "false" | "no" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            "array" => serde_json::from_str(cell).ok().filter(Value::is_array),
+            _ => serde_json::from_str(cell).ok().filter(Value::is_object),
+        };
+        if let Some(value) = value {
+            return Ok(value);
+        }
+    }
+    if types.contains(&"string") {
+        return Ok(Value::from(cell));
+    }
+
+    Err(anyhow!("expected {}", types.join(" or ")))
+}
@@ -0,0 +1,404 @@
+use crate::error::{ErrorCode, ToolsetError};
+use crate::invokables::Invokable;
+use crate::{files, json_schema, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use super::Filename;
+
+/// Nesting depth up to which schemas are rendered, guards against recursive schemas
+const MAX_REFERENCE_DEPTH: usize = 12;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SchemaDescription {
+    title: Option<String>,
+    description: Option<String>,
+    types: Vec<String>,
+    #[serde(rename = "enum")]
+    enum_values: Option<Vec<Value>>,
+    default: Option<Value>,
+    minimum: Option<Value>,
+    maximum: Option<Value>,
+    /// Whether `minimum` itself is excluded from the allowed values
+    #[serde(rename = "exclusiveMinimum")]
+    exclusive_minimum: bool,
+    /// Whether `maximum` itself is excluded from the allowed values
+    #[serde(rename = "exclusiveMaximum")]
+    exclusive_maximum: bool,
+}
+
+impl SchemaDescription {
+    fn of(root: &Value, schema: &Value) -> Self {
+        let schema = json_schema::resolve(root, schema);
+        let string_property = |key: &str| {
+            schema
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_owned())
+        };
+        let enum_values = schema
+            .get("enum")
+            .and_then(|e| e.as_array())
+            .cloned()
+            .or_else(|| schema.get("const").map(|c| vec![c.clone()]));
+        let (minimum, exclusive_minimum) = bound(schema, "minimum", "exclusiveMinimum");
+        let (maximum, exclusive_maximum) = bound(schema, "maximum", "exclusiveMaximum");
+
+        SchemaDescription {
+            title: string_property("title"),
+            description: string_property("description"),
+            types: json_schema::types(root, schema)
+                .into_iter()
+                .map(|t| t.to_owned())
+                .collect(),
+            enum_values,
+            default: schema.get("default").cloned(),
+            minimum,
+            maximum,
+            exclusive_minimum,
+            exclusive_maximum,
+        }
+    }
+}
+
+/// Returns a bound of a schema and whether it is exclusive
+///
+/// Draft 4 schemas mark `minimum` as exclusive with a boolean `exclusiveMinimum`, later drafts
+/// give the exclusive bound as number in `exclusiveMinimum` instead.
+fn bound(schema: &Value, inclusive: &str, exclusive: &str) -> (Option<Value>, bool) {
+    match schema.get(exclusive) {
+        Some(value) if value.is_number() => (Some(value.clone()), true),
+        flag => (
+            schema.get(inclusive).filter(|v| v.is_number()).cloned(),
+            flag.and_then(|f| f.as_bool()).unwrap_or(false),
+        ),
+    }
+}
+
+/// Describes the field at a JSON pointer of a file using the file's schema
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Describe {
+    file: Filename,
+    pointer: String,
+}
+
+impl Invokable for Describe {
+    type Output = SchemaDescription;
+
+    fn name() -> &'static str {
+        "json/describe"
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        if !self.pointer.is_empty() && !self.pointer.starts_with('/') {
+            return Err(anyhow!("pointer must be empty or start with `/`"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let schema_manager = state
+            .try_schema_manager()
+            .context("failed to get schema manager")?;
+        let schema = schema_manager
+            .get(Path::new(self.file.as_str()))
+            .ok_or_else(|| ToolsetError::schema_not_found(self.file.as_str()))?
+            .as_value();
+        let field = json_schema::pointer(schema, &self.pointer).ok_or_else(|| {
+            ToolsetError::new(
+                ErrorCode::PointerNotFound,
+                format!(
+                    "`{}` is not described by the schema of `{}`",
+                    self.pointer,
+                    self.file.as_str()
+                ),
+            )
+            .with_file(self.file.as_str())
+            .with_pointer(&self.pointer)
+        })?;
+
+        Ok(SchemaDescription::of(schema, field))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ReferenceFormat {
+    Markdown,
+    Html,
+}
+
+impl ReferenceFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ReferenceFormat::Markdown => "md",
+            ReferenceFormat::Html => "html",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SchemaReference {
+    index: PathBuf,
+    pages: usize,
+}
+
+/// Renders every schema the engine knows into a browsable reference in a host directory
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RenderSchemaReference {
+    dir: PathBuf,
+    format: ReferenceFormat,
+}
+
+impl Invokable for RenderSchemaReference {
+    type Output = SchemaReference;
+
+    fn name() -> &'static str {
+        "json/renderSchemaReference"
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !self.dir.is_absolute() {
+            return Err(anyhow!("output directory must be absolute"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let schema_manager = state
+            .try_schema_manager()
+            .context("failed to get schema manager")?;
+        let extension = self.format.extension();
+        let mut pages = vec![];
+
+        // Every schema gets a page, whether or not a file for it exists
+        let files: BTreeSet<String> = schema_manager.keys().map(|k| k.to_string()).collect();
+
+        for file in files {
+            let Some(schema) = schema_manager.get(Path::new(&file)) else {
+                continue;
+            };
+            let schema = schema.as_value();
+            let mut rows = vec![];
+            collect_rows(schema, schema, "", 0, &mut rows);
+
+            let page = format!("{}.{}", file.as_str(), extension);
+            let title = SchemaDescription::of(schema, schema).title;
+            let contents = match self.format {
+                ReferenceFormat::Markdown => render_markdown_page(file.as_str(), &rows),
+                ReferenceFormat::Html => render_html_page(file.as_str(), &rows),
+            };
+            files::write_atomic(self.dir.join(&page), contents)
+                .with_context(|| format!("failed to write reference for `{}`", file.as_str()))?;
+            pages.push((file.as_str().to_owned(), page, title));
+        }
+
+        let index = self.dir.join(format!("index.{}", extension));
+        let contents = match self.format {
+            ReferenceFormat::Markdown => render_markdown_index(&pages),
+            ReferenceFormat::Html => render_html_index(&pages),
+        };
+        files::write_atomic(&index, contents).context("failed to write reference index")?;
+
+        Ok(SchemaReference {
+            index,
+            pages: pages.len(),
+        })
+    }
+}
+
+/// Flattens a schema into rows of field paths, `[]` denotes array items
+fn collect_rows(
+    root: &Value,
+    schema: &Value,
+    path: &str,
+    depth: usize,
+    rows: &mut Vec<(String, SchemaDescription)>,
+) {
+    let schema = json_schema::resolve(root, schema);
+    rows.push((
+        if path.is_empty() {
+            "(root)".to_owned()
+        } else {
+            path.to_owned()
+        },
+        SchemaDescription::of(root, schema),
+    ));
+    if depth >= MAX_REFERENCE_DEPTH {
+        return;
+    }
+
+    for (key, property) in json_schema::properties(root, schema) {
+        let field = if path.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{}.{}", path, key)
+        };
+        collect_rows(root, property, &field, depth + 1, rows);
+    }
+    if let Some(items) = json_schema::items(root, schema) {
+        collect_rows(root, items, &format!("{}[]", path), depth + 1, rows);
+    }
+}
+
+fn value_list(values: &[Value]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Summarizes the allowed values of a field in a single line
+fn constraints(description: &SchemaDescription) -> String {
+    let mut parts = vec![];
+    if let Some(values) = &description.enum_values {
+        parts.push(format!("one of {}", value_list(values)));
+    }
+    if let Some(minimum) = &description.minimum {
+        if description.exclusive_minimum {
+            parts.push(format!("greater than {}", minimum));
+        } else {
+            parts.push(format!("min {}", minimum));
+        }
+    }
+    if let Some(maximum) = &description.maximum {
+        if description.exclusive_maximum {
+            parts.push(format!("less than {}", maximum));
+        } else {
+            parts.push(format!("max {}", maximum));
+        }
+    }
+    parts.join("; ")
+}
+
+fn field_text(description: &SchemaDescription) -> String {
+    match (&description.title, &description.description) {
+        (Some(title), Some(text)) => format!("{}: {}", title, text),
+        (Some(text), None) | (None, Some(text)) => text.clone(),
+        (None, None) => String::new(),
+    }
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn render_markdown_page(file: &str, rows: &[(String, SchemaDescription)]) -> String {
+    let mut md = String::new();
+    let _ = writeln!(md, "# `{}`\n", file);
+    if let Some((_, root)) = rows.first() {
+        let text = field_text(root);
+        if !text.is_empty() {
+            let _ = writeln!(md, "{}\n", text);
+        }
+    }
+    let _ = writeln!(
+        md,
+        "| Field | Type | Description | Default | Allowed values |\n| --- | --- | --- | --- | --- |"
+    );
+    for (path, description) in rows.iter().skip(1) {
+        let _ = writeln!(
+            md,
+            "| `{}` | {} | {} | {} | {} |",
+            path,
+            markdown_cell(&description.types.join(", ")),
+            markdown_cell(&field_text(description)),
+            description
+                .default
+                .as_ref()
+                .map(|d| format!("`{}`", markdown_cell(&d.to_string())))
+                .unwrap_or_default(),
+            markdown_cell(&constraints(description)),
+        );
+    }
+    md
+}
+
+fn render_markdown_index(pages: &[(String, String, Option<String>)]) -> String {
+    let mut md = String::new();
+    let _ = writeln!(md, "# JSON schema reference\n");
+    for (file, page, title) in pages {
+        let _ = match title {
+            Some(title) => writeln!(md, "- [`{}`]({}): {}", file, page, title),
+            None => writeln!(md, "- [`{}`]({})", file, page),
+        };
+    }
+    md
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+fn render_html_page(file: &str, rows: &[(String, SchemaDescription)]) -> String {
+    let mut body = String::new();
+    let _ = writeln!(body, "<h1><code>{}</code></h1>", escape_html(file));
+    if let Some((_, root)) = rows.first() {
+        let text = field_text(root);
+        if !text.is_empty() {
+            let _ = writeln!(body, "<p>{}</p>", escape_html(&text));
+        }
+    }
+    let _ = writeln!(
+        body,
+        "<table>\n<tr><th>Field</th><th>Type</th><th>Description</th><th>Default</th><th>Allowed values</th></tr>"
+    );
+    for (path, description) in rows.iter().skip(1) {
+        let _ = writeln!(
+            body,
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(path),
+            escape_html(&description.types.join(", ")),
+            escape_html(&field_text(description)),
+            description
+                .default
+                .as_ref()
+                .map(|d| format!("<code>{}</code>", escape_html(&d.to_string())))
+                .unwrap_or_default(),
+            escape_html(&constraints(description)),
+        );
+    }
+    let _ = writeln!(body, "</table>");
+    html_document(file, &body)
+}
+
+fn render_html_index(pages: &[(String, String, Option<String>)]) -> String {
+    let mut body = String::new();
+    let _ = writeln!(body, "<h1>JSON schema reference</h1>\n<ul>");
+    for (file, page, title) in pages {
+        let _ = writeln!(
+            body,
+            "<li><a href=\"{}\"><code>{}</code></a>{}</li>",
+            escape_html(page),
+            escape_html(file),
+            title
+                .as_ref()
+                .map(|t| format!(": {}", escape_html(t)))
+                .unwrap_or_default()
+        );
+    }
+    let _ = writeln!(body, "</ul>");
+    html_document("JSON schema reference", &body)
+}
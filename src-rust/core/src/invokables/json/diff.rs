@@ -0,0 +1,404 @@
+use crate::invokables::Invokable;
+use crate::state;
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write as _;
+
+use super::{item_name, localize, read_effective, read_vanilla, Filename};
+
+/// Properties that are tried in order to match items of two arrays
+const KEY_PROPERTIES: &[&str] = &[
+    "internalName",
+    "itemIndex",
+    "index",
+    "profileID",
+    "id",
+    "name",
+    "profile",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDiff {
+    field: String,
+    old_value: Option<Value>,
+    new_value: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ItemSummary {
+    key: String,
+    name: Option<String>,
+    value: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ItemDiff {
+    key: String,
+    name: Option<String>,
+    fields: Vec<FieldDiff>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiff {
+    file: String,
+    key_property: Option<String>,
+    added: Vec<ItemSummary>,
+    removed: Vec<ItemSummary>,
+    changed: Vec<ItemDiff>,
+    markdown: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Diff {
+    file: Filename,
+}
+
+impl Invokable for Diff {
+    type Output = JsonDiff;
+
+    fn name() -> &'static str {
+        "json/diff"
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let localized = localize(&state, &self.file)?;
+        let vanilla = read_vanilla(selected_mod, &localized)?;
+        let effective = read_effective(&state, &self.file).context("failed to read mod json")?;
+
+        Ok(diff_documents(self.file.as_str(), &vanilla, &effective))
+    }
+}
+
+impl JsonDiff {
+    /// Returns the new value of every changed field, keyed by item key and field
+    ///
+    /// Added and removed items are reported with the field `*`, as the whole item changes.
+    pub fn changed_fields(&self) -> BTreeMap<(String, String), Option<Value>> {
+        let mut result = BTreeMap::new();
+        for item in &self.added {
+            result.insert((item.key.clone(), "*".to_owned()), Some(item.value.clone()));
+        }
+        for item in &self.removed {
+            result.insert((item.key.clone(), "*".to_owned()), None);
+        }
+        for item in &self.changed {
+            for field in &item.fields {
+                result.insert(
+                    (item.key.clone(), field.field.clone()),
+                    field.new_value.clone(),
+                );
+            }
+        }
+        result
+    }
+}
+
+/// Compares two documents item by item
+///
+/// Arrays are matched by the first key property that identifies all items uniquely,
+/// falling back to the array index. Any other document is compared as a single item.
+pub fn diff_documents(file: &str, old: &Value, new: &Value) -> JsonDiff {
+    let mut added = vec![];
+    let mut removed = vec![];
+    let mut changed = vec![];
+    let mut key_property = None;
+
+    match (old, new) {
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            key_property = find_key_property(old_items, new_items);
+            let old_keyed = keyed_items(old_items, key_property);
+            let new_keyed = keyed_items(new_items, key_property);
+            let old_lookup: HashMap<&str, &Value> =
+                old_keyed.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+            let new_lookup: HashMap<&str, &Value> =
+                new_keyed.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+
+            for (key, old_item) in &old_keyed {
+                match new_lookup.get(key.as_str()) {
+                    Some(new_item) => {
+                        let mut fields = vec![];
+                        diff_fields("", old_item, new_item, &mut fields);
+                        if !fields.is_empty() {
+                            changed.push(ItemDiff {
+                                key: key.clone(),
+                                name: item_name(new_item),
+                                fields,
+                            });
+                        }
+                    }
+                    None => removed.push(ItemSummary {
+                        key: key.clone(),
+                        name: item_name(old_item),
+                        value: (*old_item).clone(),
+                    }),
+                }
+            }
+            for (key, new_item) in &new_keyed {
+                if !old_lookup.contains_key(key.as_str()) {
+                    added.push(ItemSummary {
+                        key: key.clone(),
+                        name: item_name(new_item),
+                        value: (*new_item).clone(),
+                    });
+                }
+            }
+        }
+        _ => {
+            let mut fields = vec![];
+            diff_fields("", old, new, &mut fields);
+            if !fields.is_empty() {
+                changed.push(ItemDiff {
+                    key: "".to_owned(),
+                    name: None,
+                    fields,
+                });
+            }
+        }
+    }
+
+    let mut diff = JsonDiff {
+        file: file.to_owned(),
+        key_property: key_property.map(|k| k.to_owned()),
+        added,
+        removed,
+        changed,
+        markdown: String::new(),
+    };
+    diff.markdown = render_markdown(&diff);
+    diff
+}
+
+fn key_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+/// Finds a property that exists in all items and is unique within both arrays
+fn find_key_property(old_items: &[Value], new_items: &[Value]) -> Option<&'static str> {
+    KEY_PROPERTIES.iter().copied().find(|property| {
+        [old_items, new_items].iter().all(|items| {
+            let mut seen = HashSet::new();
+            items.iter().all(|item| match item.get(property) {
+                Some(v) if !v.is_null() => seen.insert(key_to_string(v)),
+                _ => false,
+            })
+        })
+    })
+}
+
+fn keyed_items<'a>(items: &'a [Value], key_property: Option<&str>) -> Vec<(String, &'a Value)> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let key = match key_property.and_then(|p| item.get(p)) {
+                Some(v) => key_to_string(v),
+                None => index.to_string(),
+            };
+            (key, item)
+        })
+        .collect()
+}
+
+/// Collects differing leaf fields, recursing into objects and comparing anything else as a whole
+fn diff_fields(path: &str, old: &Value, new: &Value, fields: &mut Vec<FieldDiff>) {
+    match (old, new) {
+        (Value::Object(old_object), Value::Object(new_object)) => {
+            let keys: BTreeSet<&str> = old_object
+                .keys()
+                .chain(new_object.keys())
+                .map(|k| k.as_str())
+                .collect();
+
+            for key in keys {
+                let field = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                match (old_object.get(key), new_object.get(key)) {
+                    (Some(o), Some(n)) => diff_fields(&field, o, n, fields),
+                    (o, n) => fields.push(FieldDiff {
+                        field,
+                        old_value: o.cloned(),
+                        new_value: n.cloned(),
+                    }),
+                }
+            }
+        }
+        (o, n) => {
+            if o != n {
+                fields.push(FieldDiff {
+                    field: path.to_owned(),
+                    old_value: Some(o.clone()),
+                    new_value: Some(n.clone()),
+                });
+            }
+        }
+    }
+}
+
+fn markdown_value(value: &Option<Value>) -> String {
+    match value {
+        Some(v) => format!("`{}`", v.to_string().replace('|', "\\|")),
+        None => "—".to_owned(),
+    }
+}
+
+fn markdown_item_title(key: &str, name: &Option<String>) -> String {
+    match name {
+        Some(name) if name != key => format!("`{}` ({})", key, name),
+        _ => format!("`{}`", key),
+    }
+}
+
+fn render_markdown(diff: &JsonDiff) -> String {
+    let mut md = String::new();
+    let _ = writeln!(md, "# Changes in `{}`", diff.file);
+
+    if diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty() {
+        let _ = writeln!(md, "\nNo changes compared to vanilla.");
+        return md;
+    }
+    if !diff.added.is_empty() {
+        let _ = writeln!(md, "\n## Added\n");
+        for item in &diff.added {
+            let _ = writeln!(md, "- {}", markdown_item_title(&item.key, &item.name));
+        }
+    }
+    if !diff.removed.is_empty() {
+        let _ = writeln!(md, "\n## Removed\n");
+        for item in &diff.removed {
+            let _ = writeln!(md, "- {}", markdown_item_title(&item.key, &item.name));
+        }
+    }
+    if !diff.changed.is_empty() {
+        let _ = writeln!(md, "\n## Changed");
+        for item in &diff.changed {
+            if !item.key.is_empty() {
+                let _ = writeln!(md, "\n### {}", markdown_item_title(&item.key, &item.name));
+            }
+            let _ = writeln!(md, "\n| Field | Vanilla | Mod |\n| --- | --- | --- |");
+            for field in &item.fields {
+                let _ = writeln!(
+                    md,
+                    "| `{}` | {} | {} |",
+                    field.field,
+                    markdown_value(&field.old_value),
+                    markdown_value(&field.new_value)
+                );
+            }
+        }
+    }
+
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::vanilla_file;
+    use super::*;
+    use serde_json::json;
+
+    fn changed_fields(old: Value, new: Value) -> Vec<((String, String), Option<Value>)> {
+        diff_documents("items.json", &old, &new)
+            .changed_fields()
+            .into_iter()
+            .collect()
+    }
+
+    fn field(key: &str, field: &str, value: Option<Value>) -> ((String, String), Option<Value>) {
+        ((key.to_owned(), field.to_owned()), value)
+    }
+
+    #[test]
+    fn matches_changed_items_by_key() {
+        let old = json!([
+            { "internalName": "GLOCK", "price": 300 },
+            { "internalName": "KNIFE", "price": 20 },
+        ]);
+        let new = json!([
+            { "internalName": "KNIFE", "price": 20 },
+            { "internalName": "GLOCK", "price": 350 },
+        ]);
+
+        let diff = diff_documents("items.json", &old, &new);
+
+        assert_eq!(diff.key_property.as_deref(), Some("internalName"));
+        assert_eq!(
+            changed_fields(old, new),
+            vec![field("GLOCK", "price", Some(json!(350)))]
+        );
+    }
+
+    #[test]
+    fn reports_added_and_removed_items() {
+        let old = json!([{ "internalName": "GLOCK" }, { "internalName": "KNIFE" }]);
+        let new = json!([{ "internalName": "GLOCK" }, { "internalName": "COLT" }]);
+
+        let diff = diff_documents("items.json", &old, &new);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].key, "COLT");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].key, "KNIFE");
+        assert_eq!(
+            changed_fields(old, new),
+            vec![
+                field("COLT", "*", Some(json!({ "internalName": "COLT" }))),
+                field("KNIFE", "*", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_added_and_removed_fields() {
+        let old = json!([{ "internalName": "GLOCK", "weight": 8 }]);
+        let new = json!([{ "internalName": "GLOCK", "range": { "max": 200 } }]);
+
+        assert_eq!(
+            changed_fields(old, new),
+            vec![
+                field("GLOCK", "range", Some(json!({ "max": 200 }))),
+                field("GLOCK", "weight", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_file_only_in_mod_against_the_file_it_overrides() {
+        let localized = Filename("german/items.json".to_owned());
+        let in_vanilla = |file: &str| file == "items.json";
+
+        assert_eq!(vanilla_file(&localized, in_vanilla).as_str(), "items.json");
+        assert_eq!(
+            vanilla_file(&Filename("items.json".to_owned()), in_vanilla).as_str(),
+            "items.json"
+        );
+
+        let vanilla = json!([{ "internalName": "GLOCK", "name": "Glock 17" }]);
+        let effective = json!([{ "internalName": "GLOCK", "name": "Glock 17 (de)" }]);
+        assert_eq!(
+            changed_fields(vanilla, effective),
+            vec![field("GLOCK", "name", Some(json!("Glock 17 (de)")))]
+        );
+    }
+}
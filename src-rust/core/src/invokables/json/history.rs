@@ -0,0 +1,178 @@
+use crate::invokables::Invokable;
+use crate::journal::{self, Entry, Journal};
+use crate::l10n;
+use crate::state;
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{read_persisted, write_persisted, Filename, JsonFileWithSchema, Persisted, Read};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HistoryEntry {
+    timestamp: u64,
+    files: Vec<String>,
+    undone: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalHistory {
+    entries: Vec<HistoryEntry>,
+    can_undo: bool,
+    can_redo: bool,
+}
+
+/// Lists the persisted changes of the selected mod, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct History;
+
+impl Invokable for History {
+    type Output = JournalHistory;
+
+    fn name() -> &'static str {
+        "json/history"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let journal =
+            Journal::<Persisted>::load(selected_mod.m.id()).context("failed to load journal")?;
+        let position = journal.position();
+
+        Ok(JournalHistory {
+            entries: journal
+                .entries()
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| HistoryEntry {
+                    timestamp: entry.timestamp,
+                    files: entry.changes.iter().map(|c| c.file.clone()).collect(),
+                    undone: index >= position,
+                })
+                .collect(),
+            can_undo: position > 0,
+            can_redo: position < journal.entries().len(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JournalStepFile {
+    file: String,
+    json: JsonFileWithSchema,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JournalStep {
+    timestamp: u64,
+    files: Vec<JournalStepFile>,
+}
+
+/// Moves through the journal of the selected mod and writes the resulting state of the affected files
+///
+/// Refuses to step if any of the files differs from the state the entry expects, e.g. because it
+/// was changed outside of the toolset, as that change would be lost otherwise.
+fn step(app_state: &state::AppState, redo: bool) -> Result<JournalStep> {
+    let entry: Entry<Persisted> = {
+        let state = app_state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let _guard = journal::lock();
+        let mut journal =
+            Journal::<Persisted>::load(selected_mod.m.id()).context("failed to load journal")?;
+        let entry = if redo {
+            journal.redo().ok_or_else(|| anyhow!("nothing to redo"))?
+        } else {
+            journal.undo().ok_or_else(|| anyhow!("nothing to undo"))?
+        }
+        .clone();
+
+        let filenames: Vec<_> = entry
+            .changes
+            .iter()
+            .map(|c| Filename(c.file.clone()))
+            .collect();
+        for (filename, change) in filenames.iter().zip(&entry.changes) {
+            let expected = if redo { &change.previous } else { &change.new };
+            let current = read_persisted(selected_mod, filename)
+                .with_context(|| format!("failed to read `{}`", change.file))?;
+            if current != *expected {
+                return Err(anyhow!(
+                    "`{}` was changed since this step was recorded",
+                    change.file
+                ));
+            }
+        }
+        let targets: Vec<_> = filenames
+            .iter()
+            .zip(&entry.changes)
+            .map(|(filename, c)| (filename, if redo { &c.new } else { &c.previous }))
+            .collect();
+        write_persisted(selected_mod, &targets).context("failed to write journal step")?;
+        journal
+            .save(selected_mod.m.id())
+            .context("failed to save journal")?;
+
+        entry
+    };
+
+    let mut files = vec![];
+    for change in entry.changes {
+        // Changes of language specific variants are recorded under the variant's name, while
+        // `Read` expects the name of the resource and resolves the variant itself
+        let resource = l10n::parse_localized(&change.file)
+            .map(|(_, resource)| resource)
+            .unwrap_or_else(|| change.file.clone());
+        let json = Read {
+            file: Filename(resource),
+        }
+        .invoke(app_state)
+        .with_context(|| format!("failed to get value of `{}` after update", change.file))?;
+        files.push(JournalStepFile {
+            file: change.file,
+            json,
+        });
+    }
+
+    Ok(JournalStep {
+        timestamp: entry.timestamp,
+        files,
+    })
+}
+
+/// Reverts the most recent persisted change of the selected mod
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Undo;
+
+impl Invokable for Undo {
+    type Output = JournalStep;
+
+    fn name() -> &'static str {
+        "json/undo"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        step(state, false)
+    }
+}
+
+/// Reapplies the most recently undone change of the selected mod
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Redo;
+
+impl Invokable for Redo {
+    type Output = JournalStep;
+
+    fn name() -> &'static str {
+        "json/redo"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        step(state, true)
+    }
+}
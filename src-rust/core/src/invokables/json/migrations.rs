@@ -0,0 +1,370 @@
+use crate::state::OpenedMod;
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use super::search::collect_pointer_matches;
+use super::{persist_all, read_persisted, Filename, Persisted};
+
+/// A transform that rewrites mod data created for an older schema layout
+// Variants are only constructed by entries in `MIGRATIONS`, which may be empty
+#[allow(dead_code)]
+pub enum Migration {
+    /// A property of the objects at `items` was renamed
+    ///
+    /// `items` is a JSON pointer pattern where `*` matches any array index or key.
+    RenameField {
+        file: &'static str,
+        items: &'static str,
+        from: &'static str,
+        to: &'static str,
+    },
+    /// A file was moved to a different location
+    MoveFile {
+        from: &'static str,
+        to: &'static str,
+    },
+}
+
+pub struct RegisteredMigration {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub migration: Migration,
+}
+
+/// All known migrations, in the order they need to be applied
+///
+/// When the pinned `stracciatella` revision renames fields or moves files, add a migration here
+/// so mods created for the previous layout can be upgraded.
+pub static MIGRATIONS: &[RegisteredMigration] = &[];
+
+impl RegisteredMigration {
+    /// Returns the files that would be changed by applying this migration to a mod
+    pub fn affected_files(&self, selected_mod: &OpenedMod) -> Result<Vec<String>> {
+        let changes = self.plan(|file| read_persisted(selected_mod, file))?;
+        Ok(changes
+            .into_iter()
+            .map(|(file, _)| file.as_str().to_owned())
+            .collect())
+    }
+
+    /// Rewrites the data of a mod, all files are persisted as a single change
+    pub fn apply(&self, selected_mod: &OpenedMod) -> Result<()> {
+        let changes = self.plan(|file| read_persisted(selected_mod, file))?;
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let files: Vec<_> = changes
+            .iter()
+            .map(|(file, values)| (file, values))
+            .collect();
+        persist_all(selected_mod, &files)
+    }
+
+    /// Computes the new values and patches of all files the migration changes
+    ///
+    /// `read` returns what a mod currently stores for a file.
+    fn plan(
+        &self,
+        read: impl Fn(&Filename) -> Result<Persisted>,
+    ) -> Result<Vec<(Filename, Persisted)>> {
+        match &self.migration {
+            Migration::RenameField {
+                file,
+                items,
+                from,
+                to,
+            } => {
+                let filename = Filename(file.to_string());
+                let persisted = read(&filename)?;
+                Ok(rename_field(persisted, items, from, to)
+                    .map(|migrated| vec![(filename, migrated)])
+                    .unwrap_or_default())
+            }
+            Migration::MoveFile { from, to } => {
+                let from = Filename(from.to_string());
+                let to = Filename(to.to_string());
+                let persisted = read(&from)?;
+                if persisted.value.is_none() && persisted.patch.is_none() {
+                    return Ok(vec![]);
+                }
+                let existing = read(&to)?;
+                if existing.value.is_some() || existing.patch.is_some() {
+                    return Err(anyhow!(
+                        "cannot move `{}` to `{}` as the target already exists",
+                        from.as_str(),
+                        to.as_str()
+                    ));
+                }
+                Ok(vec![
+                    (to, persisted),
+                    (
+                        from,
+                        Persisted {
+                            value: None,
+                            patch: None,
+                        },
+                    ),
+                ])
+            }
+        }
+    }
+}
+
+/// Renames a property in the value and patch of a file, returns `None` if nothing changed
+fn rename_field(persisted: Persisted, items: &str, from: &str, to: &str) -> Option<Persisted> {
+    let pattern: Vec<_> = items.split('/').skip(1).collect();
+    let mut changed = false;
+
+    let value = persisted.value.map(|mut value| {
+        let mut matches = vec![];
+        collect_pointer_matches(&value, "", &pattern, &mut matches);
+        let pointers: Vec<String> = matches.into_iter().map(|(p, _)| p).collect();
+        for pointer in pointers {
+            if let Some(item) = value.pointer_mut(&pointer) {
+                changed |= rename_key(item, from, to);
+            }
+        }
+        value
+    });
+    let patch = persisted.patch.map(|mut patch| {
+        for operation in patch.iter_mut() {
+            for key in ["path", "from"] {
+                let renamed = operation
+                    .get(key)
+                    .and_then(|p| p.as_str())
+                    .and_then(|p| rename_in_path(p, &pattern, from, to));
+                if let Some(renamed) = renamed {
+                    operation[key] = Value::from(renamed);
+                    changed = true;
+                }
+            }
+            // Operations adding or replacing whole items carry the property in their value
+            let targets_item = operation
+                .get("path")
+                .and_then(|p| p.as_str())
+                .map(|p| matches_pattern(p, &pattern))
+                .unwrap_or(false);
+            if targets_item {
+                if let Some(value) = operation.get_mut("value") {
+                    changed |= rename_key(value, from, to);
+                }
+            }
+        }
+        patch
+    });
+
+    changed.then_some(Persisted { value, patch })
+}
+
+fn rename_key(item: &mut Value, from: &str, to: &str) -> bool {
+    match item.as_object_mut() {
+        Some(object) if !object.contains_key(to) => match object.remove(from) {
+            Some(value) => {
+                object.insert(to.to_owned(), value);
+                true
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+fn matches_pattern(path: &str, pattern: &[&str]) -> bool {
+    let tokens: Vec<_> = path.split('/').skip(1).collect();
+    tokens.len() == pattern.len()
+        && tokens
+            .iter()
+            .zip(pattern)
+            .all(|(token, expected)| *expected == "*" || token == expected)
+}
+
+/// Rewrites a JSON pointer that points into the renamed property of an item
+fn rename_in_path(path: &str, pattern: &[&str], from: &str, to: &str) -> Option<String> {
+    let mut tokens: Vec<_> = path.split('/').skip(1).collect();
+    if tokens.len() <= pattern.len() || tokens[pattern.len()] != from {
+        return None;
+    }
+    if !tokens
+        .iter()
+        .zip(pattern)
+        .all(|(token, expected)| *expected == "*" || token == expected)
+    {
+        return None;
+    }
+    tokens[pattern.len()] = to;
+    Some(format!("/{}", tokens.join("/")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    static TEST_MIGRATION: RegisteredMigration = RegisteredMigration {
+        id: "test-rename-damage",
+        description: "Rename `damage` to `impact` in weapons",
+        migration: Migration::RenameField {
+            file: "weapons.json",
+            items: "/*",
+            from: "damage",
+            to: "impact",
+        },
+    };
+
+    static TEST_MOVE: RegisteredMigration = RegisteredMigration {
+        id: "test-move-weapons",
+        description: "Move `weapons.json` to `items/weapons.json`",
+        migration: Migration::MoveFile {
+            from: "weapons.json",
+            to: "items/weapons.json",
+        },
+    };
+
+    fn empty() -> Persisted {
+        Persisted {
+            value: None,
+            patch: None,
+        }
+    }
+
+    /// Plans a migration for a mod that stores `files`, other files are not stored by the mod
+    fn plan(
+        migration: &RegisteredMigration,
+        files: &[(&str, Persisted)],
+    ) -> Result<Vec<(String, Persisted)>> {
+        let changes = migration.plan(|file| {
+            Ok(files
+                .iter()
+                .find(|(f, _)| *f == file.as_str())
+                .map(|(_, persisted)| persisted.clone())
+                .unwrap_or_else(empty))
+        })?;
+        Ok(changes
+            .into_iter()
+            .map(|(file, persisted)| (file.as_str().to_owned(), persisted))
+            .collect())
+    }
+
+    fn apply_test_migration(persisted: Persisted) -> Option<Persisted> {
+        let mut changes = plan(&TEST_MIGRATION, &[("weapons.json", persisted)]).unwrap();
+        assert!(changes.len() <= 1);
+        changes.pop().map(|(file, persisted)| {
+            assert_eq!(file, "weapons.json");
+            persisted
+        })
+    }
+
+    #[test]
+    fn renames_field_in_value() {
+        let migrated = apply_test_migration(Persisted {
+            value: Some(json!([{ "name": "Glock", "damage": 10 }, { "name": "Knife" }])),
+            patch: None,
+        });
+
+        assert_eq!(
+            migrated,
+            Some(Persisted {
+                value: Some(json!([{ "name": "Glock", "impact": 10 }, { "name": "Knife" }])),
+                patch: None,
+            })
+        );
+    }
+
+    #[test]
+    fn renames_field_in_patch() {
+        let migrated = apply_test_migration(Persisted {
+            value: None,
+            patch: Some(vec![
+                json!({ "op": "replace", "path": "/3/damage", "value": 12 }),
+                json!({ "op": "add", "path": "/4", "value": { "damage": 5 } }),
+                json!({ "op": "move", "from": "/1/damage", "path": "/2/damage" }),
+            ]),
+        });
+
+        assert_eq!(
+            migrated,
+            Some(Persisted {
+                value: None,
+                patch: Some(vec![
+                    json!({ "op": "replace", "path": "/3/impact", "value": 12 }),
+                    json!({ "op": "add", "path": "/4", "value": { "impact": 5 } }),
+                    json!({ "op": "move", "from": "/1/impact", "path": "/2/impact" }),
+                ]),
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_unaffected_data_alone() {
+        let unaffected = Persisted {
+            value: Some(json!([{ "name": "Glock", "impact": 10 }])),
+            patch: Some(vec![
+                json!({ "op": "replace", "path": "/3/name", "value": "Colt" }),
+            ]),
+        };
+
+        assert_eq!(apply_test_migration(unaffected), None);
+    }
+
+    #[test]
+    fn does_not_overwrite_existing_target() {
+        let conflicting = Persisted {
+            value: Some(json!([{ "damage": 10, "impact": 12 }])),
+            patch: None,
+        };
+
+        assert_eq!(apply_test_migration(conflicting), None);
+    }
+
+    #[test]
+    fn moves_file_in_one_change() {
+        let weapons = Persisted {
+            value: None,
+            patch: Some(vec![
+                json!({ "op": "replace", "path": "/3/damage", "value": 12 }),
+            ]),
+        };
+
+        let changes = plan(&TEST_MOVE, &[("weapons.json", weapons.clone())]).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                ("items/weapons.json".to_owned(), weapons),
+                ("weapons.json".to_owned(), empty()),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_move_of_file_not_in_mod() {
+        assert_eq!(plan(&TEST_MOVE, &[]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn does_not_move_over_existing_file() {
+        let weapons = Persisted {
+            value: Some(json!([{ "name": "Glock" }])),
+            patch: None,
+        };
+
+        let result = plan(
+            &TEST_MOVE,
+            &[
+                ("weapons.json", weapons.clone()),
+                ("items/weapons.json", weapons),
+            ],
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn migration_ids_are_unique() {
+        let mut ids: Vec<_> = MIGRATIONS.iter().map(|m| m.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        assert_eq!(ids.len(), MIGRATIONS.len());
+    }
+}
@@ -0,0 +1,570 @@
+use crate::error::{ErrorCode, ToolsetError};
+use crate::files;
+use crate::invokables::Invokable;
+use crate::journal::{self, FileChange, Journal};
+use crate::l10n;
+use crate::state::{self, OpenedMod, ToolsetState};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use stracciatella::{unicode::Nfc, vfs::VfsLayer};
+
+mod backups;
+mod bulk;
+mod compatibility;
+mod csv;
+mod describe;
+mod diff;
+mod history;
+mod migrations;
+mod references;
+mod rename;
+mod schemas;
+mod search;
+
+pub use backups::{ListBackups, RestoreBackup};
+pub use bulk::BulkUpdate;
+pub use compatibility::{check_mod_json, CheckSchemaCompatibility, IssueSource, Migrate};
+pub use csv::{ExportCsv, ImportCsv};
+pub use describe::{Describe, RenderSchemaReference};
+pub use diff::{diff_documents, Diff};
+pub use history::{History, Redo, Undo};
+pub use rename::RenameIdentifier;
+pub use schemas::ListSchemas;
+pub use search::Search;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct Filename(String);
+
+impl Filename {
+    fn validate(&self) -> Result<()> {
+        if self.0.contains("..") {
+            return Err(anyhow!("must not contain `..`"));
+        }
+        if !self.0.ends_with(".json") {
+            return Err(anyhow!("must end with `.json`"));
+        }
+        Ok(())
+    }
+
+    fn as_str(&self) -> &str {
+        self.as_ref()
+    }
+
+    fn patch_filename(&self) -> Self {
+        let mut f = self.0.strip_suffix(".json").unwrap().to_owned();
+        f.push_str(".patch.json");
+        Self(f)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Persisted {
+    value: Option<Value>,
+    patch: Option<Vec<Value>>,
+}
+
+/// How a changed document is stored in a mod
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SaveMode {
+    /// Store a JSON patch against vanilla
+    Patch,
+    /// Store the whole document, replacing vanilla
+    Replace,
+}
+
+impl SaveMode {
+    /// Returns the save mode the mod currently uses for a file
+    fn of(persisted: &Persisted) -> Self {
+        if persisted.value.is_some() && persisted.patch.is_none() {
+            SaveMode::Replace
+        } else {
+            SaveMode::Patch
+        }
+    }
+
+    /// Creates the persisted form of a changed document
+    fn to_persisted(self, vanilla: &Value, value: Value) -> Result<Persisted> {
+        Ok(match self {
+            SaveMode::Replace => Persisted {
+                value: Some(value),
+                patch: None,
+            },
+            SaveMode::Patch => {
+                let patch = json_patch::diff(vanilla, &value);
+                let patch = patch
+                    .iter()
+                    .map(serde_json::to_value)
+                    .collect::<serde_json::Result<Vec<_>>>()
+                    .context("failed to serialize patch")?;
+                Persisted {
+                    value: None,
+                    patch: Some(patch),
+                }
+            }
+        })
+    }
+}
+
+impl AsRef<str> for Filename {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JsonFileWithSchema {
+    schema: Value,
+    vanilla: Value,
+    /// Language specific file that was read instead of the requested one
+    localized: Option<String>,
+    #[serde(flatten)]
+    persisted: Persisted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Read {
+    file: Filename,
+}
+
+impl Invokable for Read {
+    type Output = JsonFileWithSchema;
+
+    fn name() -> &'static str {
+        return "json/read";
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let filename = &self.file;
+        let state = state.read();
+        let schema_manager = state
+            .try_schema_manager()
+            .context("failed to get schema manager")?;
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let schema = schema_manager
+            .get(&Path::new(filename.as_str()))
+            .ok_or_else(|| ToolsetError::schema_not_found(filename.as_str()))?;
+        let schema = schema.as_value().clone();
+        let localized = localize(&state, filename)?;
+        let vanilla = read_vanilla(selected_mod, &localized)?;
+        let persisted = read_persisted(selected_mod, &localized)?;
+
+        Ok(JsonFileWithSchema {
+            schema,
+            vanilla,
+            localized: Some(localized.0).filter(|l| l != filename.as_str()),
+            persisted,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Persist {
+    file: Filename,
+    #[serde(flatten)]
+    values: Persisted,
+}
+
+impl Invokable for Persist {
+    type Output = JsonFileWithSchema;
+
+    fn name() -> &'static str {
+        "json/persist"
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        Ok(())
+    }
+
+    fn invoke(&self, app_state: &state::AppState) -> Result<Self::Output> {
+        {
+            let state = app_state.read();
+            let selected_mod = state
+                .try_selected_mod()
+                .context("failed to get selected mod")?;
+            // Write to the same language specific file that `Read` returned
+            let localized = localize(&state, &self.file)?;
+
+            persist(selected_mod, &localized, &self.values)?;
+        }
+
+        Ok(Read {
+            file: self.file.clone(),
+        }
+        .invoke(app_state)
+        .context("failed to get value after update")?)
+    }
+}
+
+/// Reads the value and patch that a mod persisted for a file
+fn read_persisted(selected_mod: &OpenedMod, filename: &Filename) -> Result<Persisted> {
+    let path = selected_mod.data_path(filename.as_str());
+    let patch_path = selected_mod.data_path(filename.patch_filename().as_str());
+
+    let value: Option<Value> = if path.exists() {
+        let json = read_to_string(&path)?;
+        stracciatella::json::de::from_string(&json)
+            .map_err(|e| anyhow!("{}", e))
+            .context("failed to parse json")?
+    } else {
+        None
+    };
+    let patch: Option<Vec<Value>> = if patch_path.exists() {
+        let json = read_to_string(&patch_path)?;
+        stracciatella::json::de::from_string(&json)
+            .map_err(|e| anyhow!("{}", e))
+            .context("failed to parse patch json")?
+    } else {
+        None
+    };
+
+    Ok(Persisted { value, patch })
+}
+
+/// Persists a value and patch for a file in the selected mod and records the change in the journal
+fn persist(selected_mod: &OpenedMod, filename: &Filename, values: &Persisted) -> Result<()> {
+    persist_all(selected_mod, &[(filename, values)])
+}
+
+/// Persists values and patches for several files of the selected mod as a single change
+///
+/// The change is recorded as one journal entry, so undoing it reverts all files together.
+fn persist_all(selected_mod: &OpenedMod, files: &[(&Filename, &Persisted)]) -> Result<()> {
+    let _guard = journal::lock();
+    let mut journal =
+        Journal::<Persisted>::load(selected_mod.m.id()).context("failed to load journal")?;
+    let previous = write_persisted(selected_mod, files)?;
+
+    let changes: Vec<_> = files
+        .iter()
+        .zip(previous)
+        .filter_map(|((filename, values), previous)| {
+            // Empty patches are not written, so they are not recorded either
+            let new = Persisted {
+                value: values.value.clone(),
+                patch: values.patch.clone().filter(|p| !p.is_empty()),
+            };
+            (previous != new).then(|| FileChange {
+                file: filename.as_str().to_owned(),
+                previous,
+                new,
+            })
+        })
+        .collect();
+    if !changes.is_empty() {
+        journal.record(changes)?;
+        journal
+            .save(selected_mod.m.id())
+            .context("failed to save journal")?;
+    }
+
+    Ok(())
+}
+
+/// Writes values and patches for files in the selected mod and returns their previous states
+///
+/// The previous state of each file is stored as a backup first. All files are staged before any
+/// of them is moved into place. If moving a file fails, the files that were already moved are
+/// restored to their previous state. Only a crash while moving can leave an inconsistent set of
+/// files, the backups allow recovering from that.
+fn write_persisted(
+    selected_mod: &OpenedMod,
+    files: &[(&Filename, &Persisted)],
+) -> Result<Vec<Persisted>> {
+    let mut staged = vec![];
+    for (filename, values) in files {
+        staged.push(
+            StagedWrite::new(selected_mod, filename, values)
+                .with_context(|| format!("failed to stage `{}`", filename.as_str()))?,
+        );
+    }
+
+    let mut committed: Vec<StagedWrite> = vec![];
+    for (mut write, (filename, _)) in staged.into_iter().zip(files) {
+        if let Err(e) = write.commit() {
+            for write in committed.iter().rev() {
+                if let Err(restore_error) = write.restore() {
+                    log::error!(
+                        "failed to restore `{}` after a failed write: {:#}",
+                        write.path.display(),
+                        restore_error
+                    );
+                }
+            }
+            return Err(e.context(format!("failed to write `{}`", filename.as_str())));
+        }
+        committed.push(write);
+    }
+
+    Ok(committed.into_iter().map(|w| w.previous).collect())
+}
+
+/// The value and patch of a file, written to temporary files but not moved into place yet
+struct StagedWrite {
+    path: PathBuf,
+    patch_path: PathBuf,
+    previous: Persisted,
+    value: Option<files::StagedFile>,
+    patch: Option<files::StagedFile>,
+}
+
+impl StagedWrite {
+    fn new(selected_mod: &OpenedMod, filename: &Filename, values: &Persisted) -> Result<Self> {
+        let previous =
+            read_persisted(selected_mod, filename).context("failed to read previous value")?;
+        if previous.value.is_some() || previous.patch.is_some() {
+            crate::backups::create(selected_mod.m.id(), filename.as_str(), &previous)
+                .context("failed to create backup")?;
+        }
+
+        let path = selected_mod.data_path(filename.as_str());
+        let patch_path = selected_mod.data_path(filename.patch_filename().as_str());
+
+        let value = match &values.value {
+            Some(mod_value) => {
+                let content = serde_json::to_string_pretty(&mod_value)
+                    .context("failed to serialize value")?;
+                Some(files::StagedFile::write(&path, &content).context("failed to write value")?)
+            }
+            None => None,
+        };
+        let patch = match &values.patch {
+            Some(mod_patch_value) if !mod_patch_value.is_empty() => {
+                let content = serde_json::to_string_pretty(&mod_patch_value)
+                    .context("failed to serialize patch value")?;
+                Some(
+                    files::StagedFile::write(&patch_path, &content)
+                        .context("failed to write patch value")?,
+                )
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            path,
+            patch_path,
+            previous,
+            value,
+            patch,
+        })
+    }
+
+    /// Moves the value and patch into place
+    ///
+    /// If moving the patch fails after the value was moved, the previous value is restored.
+    fn commit(&mut self) -> Result<()> {
+        match self.value.take() {
+            Some(staged) => staged.commit().context("failed to write value")?,
+            None => files::remove_file(&self.path).context("failed to delete value")?,
+        }
+        let patch_result = match self.patch.take() {
+            Some(staged) => staged.commit().context("failed to write patch value"),
+            None => files::remove_file(&self.patch_path).context("failed to delete patch value"),
+        };
+        if let Err(e) = patch_result {
+            // The previous patch is still in place, so the value needs to match it again
+            if let Err(restore_error) = restore_file(&self.path, self.previous.value.as_ref()) {
+                log::error!(
+                    "failed to restore `{}` after a failed write: {:#}",
+                    self.path.display(),
+                    restore_error
+                );
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Writes the previous value and patch back after a successful commit
+    fn restore(&self) -> Result<()> {
+        restore_file(&self.path, self.previous.value.as_ref())?;
+        restore_file(&self.patch_path, self.previous.patch.as_ref())
+    }
+}
+
+fn restore_file<T: Serialize>(path: &Path, value: Option<&T>) -> Result<()> {
+    match value {
+        Some(value) => {
+            let content =
+                serde_json::to_string_pretty(value).context("failed to serialize value")?;
+            files::write_atomic(path, content)
+        }
+        None => files::remove_file(path).context("failed to delete value"),
+    }
+}
+
+/// Lists all files that have a schema and exist in the engine assets, a required mod or the
+/// selected mod
+fn list_schema_files(state: &ToolsetState) -> Result<Vec<Filename>> {
+    let selected_mod = state
+        .try_selected_mod()
+        .context("failed to get selected mod")?;
+    let mut mod_dirs = selected_mod.required_data_dirs();
+    mod_dirs.push(selected_mod.data_path(""));
+    let config = state.try_config().context("failed to get config")?;
+    let schema_manager = state
+        .try_schema_manager()
+        .context("failed to get schema manager")?;
+    let externalized_dir = Path::new(&config.stracciatella_install_dir).join("externalized");
+    let mut result = BTreeSet::new();
+
+    for dir in std::iter::once(externalized_dir).chain(mod_dirs) {
+        let candidates = files::list_files_recursive(&dir)
+            .with_context(|| format!("failed to list files in `{}`", dir.display()))?;
+        for candidate in candidates {
+            let candidate = files::to_resource_path(&candidate);
+            if candidate.ends_with(".json")
+                && !candidate.ends_with(".patch.json")
+                && schema_manager.get(Path::new(&candidate)).is_some()
+            {
+                result.insert(candidate);
+            }
+        }
+    }
+
+    Ok(result.into_iter().map(Filename).collect())
+}
+
+/// Returns the language specific variant of a file, which edits of the file are written to
+fn localize(state: &ToolsetState, filename: &Filename) -> Result<Filename> {
+    Ok(Filename(l10n::localize(state, filename.as_str())?))
+}
+
+/// Returns the file the vanilla document of a file is read from
+///
+/// A language specific variant might only exist in the mod, vanilla falls back to the file it
+/// overrides then. `in_vanilla` tells whether vanilla provides a file.
+fn vanilla_file(filename: &Filename, in_vanilla: impl Fn(&str) -> bool) -> Filename {
+    match l10n::parse_localized(filename.as_str()) {
+        Some((_, base)) if !in_vanilla(filename.as_str()) => Filename(base),
+        _ => filename.clone(),
+    }
+}
+
+/// Reads the vanilla document of a file
+fn read_vanilla(selected_mod: &OpenedMod, filename: &Filename) -> Result<Value> {
+    let vfs = &selected_mod.vfs;
+    let file = vanilla_file(filename, |file| vfs.open(&Nfc::caseless(file)).is_ok());
+
+    // Other failures, e.g. invalid json, are not a missing file
+    if let Err(e) = vfs.open(&Nfc::caseless(file.as_str())) {
+        if e.kind() == ErrorKind::NotFound {
+            return Err(anyhow::Error::new(e).context(
+                ToolsetError::new(ErrorCode::FileNotFound, "failed to read vanilla json")
+                    .with_file(file.as_str()),
+            ));
+        }
+    }
+    vfs.read_patched_json(&Nfc::caseless(file.as_str()))
+        .context("failed to read vanilla json")
+}
+
+/// Reads the document the engine would see for a file in the configured language, with the mod
+/// value or patch applied
+fn read_effective(state: &ToolsetState, filename: &Filename) -> Result<Value> {
+    let selected_mod = state
+        .try_selected_mod()
+        .context("failed to get selected mod")?;
+    let localized = localize(state, filename)?;
+    let vanilla = read_vanilla(selected_mod, &localized);
+    let persisted = read_persisted(selected_mod, &localized)?;
+
+    let mut value = match persisted.value {
+        Some(value) => value,
+        None => vanilla?,
+    };
+    if let Some(patch) = persisted.patch {
+        apply_patch(&mut value, patch).context("failed to apply patch")?;
+    }
+
+    Ok(value)
+}
+
+pub fn apply_patch(value: &mut Value, patch: Vec<Value>) -> Result<()> {
+    let patch: json_patch::Patch =
+        serde_json::from_value(Value::Array(patch)).context("failed to parse patch")?;
+    json_patch::patch(value, &patch)?;
+    Ok(())
+}
+
+/// Returns a human readable name for an item of a JSON file, if it has one
+fn item_name(item: &Value) -> Option<String> {
+    ["internalName", "name", "profile"]
+        .iter()
+        .find_map(|key| item.get(key).and_then(|v| v.as_str()))
+        .map(|v| v.to_owned())
+}
+
+/// Resolves a dot separated field path inside of an item
+fn field_value<'a>(item: &'a Value, field: &str) -> Option<&'a Value> {
+    field
+        .split('.')
+        .try_fold(item, |current, key| match current {
+            Value::Object(o) => o.get(key),
+            Value::Array(a) => a.get(key.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+/// Sets a field inside of an item, creating intermediate objects as necessary
+fn set_field(item: &mut Value, field: &str, value: Value) -> Result<()> {
+    let mut current = item;
+    let mut keys = field.split('.').peekable();
+
+    while let Some(key) = keys.next() {
+        let is_last = keys.peek().is_none();
+        current = match current {
+            Value::Object(o) => {
+                if is_last {
+                    o.insert(key.to_owned(), value);
+                    return Ok(());
+                }
+                o.entry(key.to_owned())
+                    .or_insert_with(|| Value::Object(Map::new()))
+            }
+            Value::Array(a) => {
+                let index = key
+                    .parse::<usize>()
+                    .with_context(|| format!("`{}` is not an array index", key))?;
+                let element = a
+                    .get_mut(index)
+                    .ok_or_else(|| anyhow!("array index {} out of bounds", index))?;
+                if is_last {
+                    *element = value;
+                    return Ok(());
+                }
+                element
+            }
+            _ => return Err(anyhow!("cannot set `{}` on a non-object value", key)),
+        };
+    }
+
+    Err(anyhow!("field must not be empty"))
+}
+
+/// Appends a reference token to a JSON pointer, escaping it as necessary
+fn pointer_push(pointer: &str, token: &str) -> String {
+    format!(
+        "{}/{}",
+        pointer,
+        token.replace('~', "~0").replace('/', "~1")
+    )
+}
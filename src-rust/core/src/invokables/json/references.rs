@@ -0,0 +1,247 @@
+//! Map of string identifiers defined in JSON files and the places that refer to them
+//!
+//! The stracciatella schemas do not describe which strings refer to identifiers of other
+//! files, so the map is a table maintained here that mirrors the reference widgets of the
+//! editor. It needs to be extended when the engine adds new references. An entry is only
+//! used if the schema of its file still describes a string at its location.
+
+use crate::json_schema;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::Path;
+use stracciatella::schemas::SchemaManager;
+
+use super::{pointer_push, Filename};
+
+/// A file that defines identifiers in a property of each of its items
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentifierSource {
+    pub file: String,
+    pub property: String,
+}
+
+/// A location in a file that refers to an identifier
+///
+/// `pointer` is a JSON pointer pattern where `*` matches any single reference token, `targets`
+/// are the files that define the identifiers that may be referenced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub file: String,
+    pub pointer: String,
+    pub targets: Vec<String>,
+}
+
+const ITEMS: &[&str] = &[
+    "armours.json",
+    "explosives.json",
+    "items.json",
+    "magazines.json",
+    "weapons.json",
+];
+
+/// Files defining identifiers and the property of their items that holds them
+const IDENTIFIER_SOURCES: &[(&str, &str)] = &[
+    ("ammo-types.json", "internalName"),
+    ("armours.json", "internalName"),
+    ("army-compositions.json", "name"),
+    ("calibres.json", "internalName"),
+    ("explosion-animations.json", "name"),
+    ("explosive-calibres.json", "internalName"),
+    ("explosives.json", "internalName"),
+    ("items.json", "internalName"),
+    ("loading-screens.json", "internalName"),
+    ("magazines.json", "internalName"),
+    ("mercs-profile-info.json", "internalName"),
+    ("strategic-map-towns.json", "internalName"),
+    ("weapons.json", "internalName"),
+];
+
+/// Files with the pointer patterns of their references and the files they refer to
+const REFERENCES: &[(&str, &str, &[&str])] = &[
+    (
+        "army-garrison-groups.json",
+        "/*/composition",
+        &["army-compositions.json"],
+    ),
+    ("army-gun-choice-extended.json", "/*/*", &["weapons.json"]),
+    ("army-gun-choice-normal.json", "/*/*", &["weapons.json"]),
+    ("dealers.json", "/*/profile", &["mercs-profile-info.json"]),
+    (
+        "explosion-animations.json",
+        "/*/waterAnimation",
+        &["explosion-animations.json"],
+    ),
+    (
+        "explosives.json",
+        "/*/calibre",
+        &["explosive-calibres.json"],
+    ),
+    (
+        "explosives.json",
+        "/*/animation",
+        &["explosion-animations.json"],
+    ),
+    ("imp.json", "/inventory/*", ITEMS),
+    ("imp.json", "/if_normal_shooter/*", ITEMS),
+    ("imp.json", "/if_good_shooter/*", ITEMS),
+    (
+        "loading-screens-mapping.json",
+        "/*/day",
+        &["loading-screens.json"],
+    ),
+    (
+        "loading-screens-mapping.json",
+        "/*/night",
+        &["loading-screens.json"],
+    ),
+    ("magazines.json", "/*/ammoType", &["ammo-types.json"]),
+    ("magazines.json", "/*/calibre", &["calibres.json"]),
+    (
+        "magazines.json",
+        "/*/standardReplacement",
+        &["magazines.json"],
+    ),
+    (
+        "mercs-MERC-listings.json",
+        "/*/profile",
+        &["mercs-profile-info.json"],
+    ),
+    (
+        "mercs-MERC-listings.json",
+        "/*/quotes/*/profile",
+        &["mercs-profile-info.json"],
+    ),
+    ("mercs-profile-info.json", "/*/inventory/*/item", ITEMS),
+    (
+        "mercs-relations.json",
+        "/*/profile",
+        &["mercs-profile-info.json"],
+    ),
+    (
+        "mercs-relations.json",
+        "/*/relations/*/target",
+        &["mercs-profile-info.json"],
+    ),
+    (
+        "mercs-rpc-small-faces.json",
+        "/*/profile",
+        &["mercs-profile-info.json"],
+    ),
+    (
+        "script-records-NPCs.json",
+        "/*/profile",
+        &["mercs-profile-info.json"],
+    ),
+    (
+        "script-records-NPCs.json",
+        "/*/records/*/requiredItem",
+        ITEMS,
+    ),
+    (
+        "script-records-NPCs.json",
+        "/*/records/*/triggerNPC",
+        &["mercs-profile-info.json"],
+    ),
+    ("script-records-NPCs.json", "/*/records/*/giftItem", ITEMS),
+    (
+        "script-records-control.json",
+        "/meanwhiles/*/chars/*/name",
+        &["mercs-profile-info.json"],
+    ),
+    (
+        "strategic-map-npc-placements.json",
+        "/*/profile",
+        &["mercs-profile-info.json"],
+    ),
+    (
+        "strategic-mines.json",
+        "/*/associatedTown",
+        &["strategic-map-towns.json"],
+    ),
+    ("tactical-map-item-replacements.json", "/*/from", ITEMS),
+    ("vehicles.json", "/*/profile", &["mercs-profile-info.json"]),
+    ("vehicles.json", "/*/armourType", &["armours.json"]),
+    ("weapons.json", "/*/calibre", &["calibres.json"]),
+    ("weapons.json", "/*/standardReplacement", &["weapons.json"]),
+];
+
+/// Identifier sources and references of a set of files
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceMap {
+    sources: Vec<IdentifierSource>,
+    references: Vec<Reference>,
+}
+
+impl ReferenceMap {
+    /// Collects the sources and references of the given files that match their schemas
+    pub fn for_files(schema_manager: &SchemaManager, files: &[Filename]) -> Self {
+        let mut map = Self::default();
+
+        for file in files {
+            let Some(schema) = schema_manager.get(Path::new(file.as_str())) else {
+                continue;
+            };
+            let root = schema.as_value();
+
+            map.sources.extend(
+                IDENTIFIER_SOURCES
+                    .iter()
+                    .filter(|(f, property)| {
+                        *f == file.as_str() && is_string(root, &pointer_push("/*", property))
+                    })
+                    .map(|(f, property)| IdentifierSource {
+                        file: (*f).to_owned(),
+                        property: (*property).to_owned(),
+                    }),
+            );
+            map.references.extend(
+                REFERENCES
+                    .iter()
+                    .filter(|(f, pointer, _)| *f == file.as_str() && is_string(root, pointer))
+                    .map(|(f, pointer, targets)| Reference {
+                        file: (*f).to_owned(),
+                        pointer: (*pointer).to_owned(),
+                        targets: targets.iter().map(|t| (*t).to_owned()).collect(),
+                    }),
+            );
+        }
+
+        map
+    }
+
+    /// Returns the identifier source for a file, if it defines identifiers
+    pub fn source(&self, file: &str) -> Option<&IdentifierSource> {
+        self.sources.iter().find(|s| s.file == file)
+    }
+
+    /// Returns all sources that share a namespace with the identifiers of `file`
+    ///
+    /// Identifiers of files that can be referenced from the same location must be unique
+    /// across all of these files, e.g. an item name must not be used by a weapon as well.
+    pub fn namespace(&self, file: &str) -> Vec<&IdentifierSource> {
+        let mut files: BTreeSet<&str> = BTreeSet::from([file]);
+        for reference in self.references_to(file) {
+            files.extend(reference.targets.iter().map(|t| t.as_str()));
+        }
+        self.sources
+            .iter()
+            .filter(|s| files.contains(s.file.as_str()))
+            .collect()
+    }
+
+    /// Returns all references that may refer to identifiers of `file`
+    pub fn references_to<'a>(&'a self, file: &'a str) -> impl Iterator<Item = &'a Reference> + 'a {
+        self.references
+            .iter()
+            .filter(move |r| r.targets.iter().any(|t| t == file))
+    }
+}
+
+/// Checks whether the schema describes a string at a pointer pattern
+fn is_string(root: &Value, pattern: &str) -> bool {
+    // Any index or key selects the schema of the items or additional properties
+    let pointer = pattern.replace("/*", "/0");
+    json_schema::pointer(root, &pointer)
+        .map(|schema| json_schema::types(root, schema).contains(&"string"))
+        .unwrap_or(false)
+}
@@ -0,0 +1,230 @@
+use crate::error::{ErrorCode, ToolsetError};
+use crate::invokables::Invokable;
+use crate::state;
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use super::references::ReferenceMap;
+use super::search::collect_pointer_matches;
+use super::{
+    item_name, list_schema_files, localize, persist_all, pointer_push, read_effective,
+    read_persisted, Filename, Persisted,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentifierChange {
+    file: String,
+    pointer: String,
+    item_name: Option<String>,
+    old_value: Value,
+    new_value: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RenameIdentifierResult {
+    changes: Vec<IdentifierChange>,
+    applied: bool,
+}
+
+/// Renames an identifier (e.g. an item's `internalName`) and updates all references to it
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RenameIdentifier {
+    file: Filename,
+    from: String,
+    to: String,
+    preview: Option<bool>,
+}
+
+impl Invokable for RenameIdentifier {
+    type Output = RenameIdentifierResult;
+
+    fn name() -> &'static str {
+        "json/renameIdentifier"
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.file
+            .validate()
+            .context("failed to validate filename")?;
+        if self.from.is_empty() {
+            return Err(anyhow!("old identifier must not be empty"));
+        }
+        if self.to.is_empty() {
+            return Err(anyhow!("new identifier must not be empty"));
+        }
+        if self.from == self.to {
+            return Err(anyhow!("new identifier must differ from the old one"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let schema_manager = state
+            .try_schema_manager()
+            .context("failed to get schema manager")?;
+        let files = list_schema_files(&state).context("failed to list schema files")?;
+        let references = ReferenceMap::for_files(schema_manager, &files);
+        let source = references
+            .source(self.file.as_str())
+            .ok_or_else(|| anyhow!("`{}` does not define identifiers", self.file.as_str()))?;
+
+        for other in references.namespace(self.file.as_str()) {
+            let value = read_effective(&state, &Filename(other.file.clone()))
+                .with_context(|| format!("failed to read `{}`", other.file))?;
+            if find_items(&value, &other.property, &self.to)
+                .next()
+                .is_some()
+            {
+                return Err(anyhow!(
+                    "identifier `{}` already exists in `{}`",
+                    self.to,
+                    other.file
+                ));
+            }
+        }
+
+        let mut changes = vec![];
+        let value = read_effective(&state, &self.file)
+            .with_context(|| format!("failed to read `{}`", self.file.as_str()))?;
+        for (index, item) in find_items(&value, &source.property, &self.from) {
+            changes.push(IdentifierChange {
+                file: self.file.as_str().to_owned(),
+                pointer: pointer_push(&pointer_push("", &index.to_string()), &source.property),
+                item_name: item_name(item),
+                old_value: json!(self.from),
+                new_value: json!(self.to),
+            });
+        }
+        if changes.is_empty() {
+            return Err(anyhow!(
+                "identifier `{}` not found in `{}`",
+                self.from,
+                self.file.as_str()
+            ));
+        }
+
+        for reference in references.references_to(self.file.as_str()) {
+            let value = match read_effective(&state, &Filename(reference.file.clone())) {
+                Ok(value) => value,
+                Err(e) => {
+                    log::warn!("skipping references in `{}`: {:#}", reference.file, e);
+                    continue;
+                }
+            };
+            let tokens: Vec<_> = reference.pointer.split('/').skip(1).collect();
+            let mut matches = vec![];
+            collect_pointer_matches(&value, "", &tokens, &mut matches);
+
+            for (pointer, matched) in matches {
+                if matched.as_str() != Some(self.from.as_str()) {
+                    continue;
+                }
+                let item_name = pointer
+                    .split('/')
+                    .nth(1)
+                    .and_then(|index| value.pointer(&format!("/{}", index)))
+                    .and_then(item_name);
+                changes.push(IdentifierChange {
+                    file: reference.file.clone(),
+                    pointer,
+                    item_name,
+                    old_value: json!(self.from),
+                    new_value: json!(self.to),
+                });
+            }
+        }
+
+        let preview = self.preview.unwrap_or(false);
+        if !preview {
+            let mut changes_by_file: BTreeMap<&str, Vec<&IdentifierChange>> = BTreeMap::new();
+            for change in &changes {
+                changes_by_file
+                    .entry(change.file.as_str())
+                    .or_default()
+                    .push(change);
+            }
+
+            // All files are written as one change, so a failure leaves none of them updated and
+            // undo reverts the whole rename
+            let mut updates = vec![];
+            for (file, file_changes) in changes_by_file {
+                let filename = localize(&state, &Filename(file.to_owned()))?;
+                let persisted = read_persisted(selected_mod, &filename)
+                    .with_context(|| format!("failed to read `{}` from mod", file))?;
+                let updated = apply_changes(persisted, &file_changes)
+                    .with_context(|| format!("failed to update `{}`", file))?;
+                updates.push((filename, updated));
+            }
+            let updates: Vec<_> = updates.iter().map(|(f, p)| (f, p)).collect();
+            persist_all(selected_mod, &updates).context("failed to persist renamed identifier")?;
+        }
+
+        Ok(RenameIdentifierResult {
+            changes,
+            applied: !preview,
+        })
+    }
+}
+
+/// Finds all top-level items whose `property` equals `identifier`
+fn find_items<'a>(
+    value: &'a Value,
+    property: &'a str,
+    identifier: &'a str,
+) -> impl Iterator<Item = (usize, &'a Value)> + 'a {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter(move |(_, item)| item.get(property).and_then(|v| v.as_str()) == Some(identifier))
+}
+
+/// Applies changes to the mod's persisted value or patch
+///
+/// Changes are appended to the patch, unless the mod replaces the whole file without a patch.
+fn apply_changes(persisted: Persisted, changes: &[&IdentifierChange]) -> Result<Persisted> {
+    match persisted {
+        Persisted {
+            value: Some(mut value),
+            patch: None,
+        } => {
+            for change in changes {
+                let target = value.pointer_mut(&change.pointer).ok_or_else(|| {
+                    ToolsetError::new(
+                        ErrorCode::PointerNotFound,
+                        format!("`{}` not found in mod value", change.pointer),
+                    )
+                    .with_pointer(&change.pointer)
+                })?;
+                *target = change.new_value.clone();
+            }
+            Ok(Persisted {
+                value: Some(value),
+                patch: None,
+            })
+        }
+        Persisted { value, patch } => {
+            let mut patch = patch.unwrap_or_default();
+            for change in changes {
+                patch.push(json!({
+                    "op": "replace",
+                    "path": change.pointer,
+                    "value": change.new_value,
+                }));
+            }
+            Ok(Persisted {
+                value,
+                patch: Some(patch),
+            })
+        }
+    }
+}
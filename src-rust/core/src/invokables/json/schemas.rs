@@ -0,0 +1,68 @@
+use crate::invokables::Invokable;
+use crate::state;
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::{list_schema_files, localize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaEntry {
+    file: String,
+    title: Option<String>,
+    description: Option<String>,
+    has_value: bool,
+    has_patch: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListSchemas;
+
+impl Invokable for ListSchemas {
+    type Output = Vec<SchemaEntry>;
+
+    fn name() -> &'static str {
+        "json/listSchemas"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let schema_manager = state
+            .try_schema_manager()
+            .context("failed to get schema manager")?;
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let files = list_schema_files(&state).context("failed to list schema files")?;
+        let mut entries = vec![];
+
+        for file in files {
+            let Some(schema) = schema_manager.get(Path::new(file.as_str())) else {
+                continue;
+            };
+            let schema = schema.as_value();
+            // Edits of the file are stored in its language specific variant
+            let localized = localize(&state, &file)?;
+            let string_property = |key: &str| {
+                schema
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_owned())
+            };
+
+            entries.push(SchemaEntry {
+                title: string_property("title"),
+                description: string_property("description"),
+                has_value: selected_mod.data_path(localized.as_str()).exists(),
+                has_patch: selected_mod
+                    .data_path(localized.patch_filename().as_str())
+                    .exists(),
+                file: file.as_str().to_owned(),
+            });
+        }
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,259 @@
+use crate::invokables::Invokable;
+use crate::state;
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{field_value, item_name, list_schema_files, pointer_push, read_effective};
+
+/// A condition on a single field of a JSON item
+///
+/// `field` is a dot separated path into the item, e.g. `inventoryGraphics.small.path`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum Predicate {
+    Equals {
+        field: String,
+        value: Value,
+    },
+    Contains {
+        field: String,
+        value: Value,
+    },
+    Range {
+        field: String,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+}
+
+impl Predicate {
+    pub fn validate(&self) -> Result<()> {
+        let field = match self {
+            Predicate::Equals { field, .. } => field,
+            Predicate::Contains { field, .. } => field,
+            Predicate::Range { field, min, max } => {
+                if min.is_none() && max.is_none() {
+                    return Err(anyhow!("range needs at least one of `min` or `max`"));
+                }
+                field
+            }
+        };
+        if field.is_empty() {
+            return Err(anyhow!("field must not be empty"));
+        }
+        Ok(())
+    }
+
+    /// Returns whether the item matches the predicate
+    pub fn matches(&self, item: &Value) -> bool {
+        match self {
+            Predicate::Equals { field, value } => field_value(item, field) == Some(value),
+            Predicate::Contains { field, value } => match (field_value(item, field), value) {
+                (Some(Value::String(s)), Value::String(v)) => {
+                    s.to_lowercase().contains(&v.to_lowercase())
+                }
+                (Some(Value::Array(a)), v) => a.contains(v),
+                _ => false,
+            },
+            Predicate::Range { field, min, max } => {
+                match field_value(item, field).and_then(|v| v.as_f64()) {
+                    Some(v) => min.is_none_or(|min| v >= min) && max.is_none_or(|max| v <= max),
+                    None => false,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SearchQuery {
+    /// Matches string values containing the text and other values equal to it
+    #[serde(rename_all = "camelCase")]
+    Text {
+        text: String,
+        case_sensitive: Option<bool>,
+    },
+    /// Matches all values at a JSON pointer, `*` matches any single reference token
+    Pointer { pattern: String },
+    /// Matches all top-level items that satisfy the predicate
+    Predicate { predicate: Predicate },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    file: String,
+    pointer: String,
+    item_name: Option<String>,
+    value: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Search {
+    query: SearchQuery,
+}
+
+impl Invokable for Search {
+    type Output = Vec<SearchHit>;
+
+    fn name() -> &'static str {
+        "json/search"
+    }
+
+    fn validate(&self) -> Result<()> {
+        match &self.query {
+            SearchQuery::Text { text, .. } => {
+                if text.is_empty() {
+                    return Err(anyhow!("text must not be empty"));
+                }
+            }
+            SearchQuery::Pointer { pattern } => {
+                if !pattern.is_empty() && !pattern.starts_with('/') {
+                    return Err(anyhow!("pointer pattern must start with `/`"));
+                }
+            }
+            SearchQuery::Predicate { predicate } => predicate.validate()?,
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let files = list_schema_files(&state).context("failed to list schema files")?;
+        let mut hits = vec![];
+
+        for file in files {
+            let value = match read_effective(&state, &file) {
+                Ok(value) => value,
+                Err(e) => {
+                    log::warn!("skipping `{}` in search: {:#}", file.as_str(), e);
+                    continue;
+                }
+            };
+            let mut matches = vec![];
+            match &self.query {
+                SearchQuery::Text {
+                    text,
+                    case_sensitive,
+                } => {
+                    let case_sensitive = case_sensitive.unwrap_or(false);
+                    let text = if case_sensitive {
+                        text.clone()
+                    } else {
+                        text.to_lowercase()
+                    };
+                    collect_text_matches(&value, "", &text, case_sensitive, &mut matches);
+                }
+                SearchQuery::Pointer { pattern } => {
+                    let tokens: Vec<_> = pattern.split('/').skip(1).collect();
+                    collect_pointer_matches(&value, "", &tokens, &mut matches);
+                }
+                SearchQuery::Predicate { predicate } => match &value {
+                    Value::Array(items) => {
+                        for (index, item) in items.iter().enumerate() {
+                            if predicate.matches(item) {
+                                matches.push((pointer_push("", &index.to_string()), item));
+                            }
+                        }
+                    }
+                    item => {
+                        if predicate.matches(item) {
+                            matches.push(("".to_owned(), item));
+                        }
+                    }
+                },
+            }
+
+            for (pointer, matched) in matches {
+                hits.push(SearchHit {
+                    file: file.as_str().to_owned(),
+                    item_name: surrounding_item_name(&value, &pointer),
+                    pointer,
+                    value: matched.clone(),
+                });
+            }
+        }
+
+        Ok(hits)
+    }
+}
+
+fn collect_text_matches<'a>(
+    value: &'a Value,
+    pointer: &str,
+    text: &str,
+    case_sensitive: bool,
+    matches: &mut Vec<(String, &'a Value)>,
+) {
+    let is_match = match value {
+        Value::Array(a) => {
+            for (index, v) in a.iter().enumerate() {
+                let pointer = pointer_push(pointer, &index.to_string());
+                collect_text_matches(v, &pointer, text, case_sensitive, matches);
+            }
+            false
+        }
+        Value::Object(o) => {
+            for (key, v) in o {
+                let pointer = pointer_push(pointer, key);
+                collect_text_matches(v, &pointer, text, case_sensitive, matches);
+            }
+            false
+        }
+        Value::String(s) if case_sensitive => s.contains(text),
+        Value::String(s) => s.to_lowercase().contains(text),
+        Value::Number(n) => n.to_string() == text,
+        Value::Bool(b) => b.to_string() == text,
+        Value::Null => false,
+    };
+    if is_match {
+        matches.push((pointer.to_owned(), value));
+    }
+}
+
+pub fn collect_pointer_matches<'a>(
+    value: &'a Value,
+    pointer: &str,
+    tokens: &[&str],
+    matches: &mut Vec<(String, &'a Value)>,
+) {
+    let Some((token, rest)) = tokens.split_first() else {
+        matches.push((pointer.to_owned(), value));
+        return;
+    };
+    let token = token.replace("~1", "/").replace("~0", "~");
+
+    match value {
+        Value::Array(a) => {
+            for (index, v) in a.iter().enumerate() {
+                if token == "*" || token == index.to_string() {
+                    let pointer = pointer_push(pointer, &index.to_string());
+                    collect_pointer_matches(v, &pointer, rest, matches);
+                }
+            }
+        }
+        Value::Object(o) => {
+            for (key, v) in o {
+                if token == "*" || &token == key {
+                    let pointer = pointer_push(pointer, key);
+                    collect_pointer_matches(v, &pointer, rest, matches);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Finds the name of the top-level item that contains the value at `pointer`
+fn surrounding_item_name(document: &Value, pointer: &str) -> Option<String> {
+    match document {
+        Value::Array(_) => {
+            let index = pointer.split('/').nth(1)?;
+            document.pointer(&format!("/{}", index)).and_then(item_name)
+        }
+        _ => item_name(document),
+    }
+}
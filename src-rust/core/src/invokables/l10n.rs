@@ -0,0 +1,76 @@
+use crate::l10n::{parse_localized, Language};
+use crate::{files, invokables::Invokable, state};
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LocalizedResource {
+    /// Path of the language specific file
+    file: String,
+    /// Resource that is overridden by the file
+    resource: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LanguageCoverage {
+    language: Language,
+    resources: Vec<LocalizedResource>,
+    /// Resources that are localized for other languages, but not for this one
+    missing: Vec<String>,
+}
+
+/// Lists the localized resources of the selected mod for each language
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Coverage;
+
+impl Invokable for Coverage {
+    type Output = Vec<LanguageCoverage>;
+
+    fn name() -> &'static str {
+        "l10n/coverage"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let data_dir = selected_mod.data_path("");
+        let mut by_language: BTreeMap<Language, Vec<LocalizedResource>> = BTreeMap::new();
+        let mut all_resources = BTreeSet::new();
+
+        for path in files::list_files_recursive(&data_dir)
+            .with_context(|| format!("failed to list files in `{}`", data_dir.display()))?
+        {
+            let file = files::to_resource_path(&path);
+            if let Some((language, resource)) = parse_localized(&file) {
+                all_resources.insert(resource.clone());
+                by_language
+                    .entry(language)
+                    .or_default()
+                    .push(LocalizedResource { file, resource });
+            }
+        }
+
+        Ok(Language::ALL
+            .into_iter()
+            .map(|language| {
+                let resources = by_language.remove(&language).unwrap_or_default();
+                let covered: BTreeSet<&str> =
+                    resources.iter().map(|r| r.resource.as_str()).collect();
+                let missing = all_resources
+                    .iter()
+                    .filter(|r| !covered.contains(r.as_str()))
+                    .cloned()
+                    .collect();
+                LanguageCoverage {
+                    language,
+                    resources,
+                    missing,
+                }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,207 @@
+use crate::error::{ErrorCode, InvokeError, ToolsetError};
+use crate::state::AppState;
+use anyhow::{Context, Result};
+use schemars::{schema_for, JsonSchema};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+mod edt;
+mod image;
+mod json;
+mod l10n;
+mod mods;
+mod resources;
+mod sounds;
+mod text;
+mod toolset;
+
+pub trait Invokable: DeserializeOwned + JsonSchema {
+    type Output: Serialize + JsonSchema;
+
+    fn name() -> &'static str;
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+    fn invoke(&self, state: &AppState) -> Result<Self::Output>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InvokePayload {
+    func: String,
+    params: serde_json::Value,
+}
+
+/// Name and JSON Schemas of the parameters and result of an invokable
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InvokableDescription {
+    pub name: &'static str,
+    pub input_schema: Value,
+    pub output_schema: Value,
+}
+
+impl InvokableDescription {
+    fn of<I: Invokable>() -> Result<Self> {
+        Ok(Self {
+            name: I::name(),
+            input_schema: serde_json::to_value(schema_for!(I))
+                .context("failed to serialize input schema")?,
+            output_schema: serde_json::to_value(schema_for!(I::Output))
+                .context("failed to serialize output schema")?,
+        })
+    }
+}
+
+type Executable = Box<dyn Fn(&AppState, Value) -> Result<Value> + Send + Sync>;
+
+struct Registration {
+    execute: Executable,
+    describe: fn() -> Result<InvokableDescription>,
+}
+
+pub struct Invokables {
+    registered: HashMap<&'static str, Registration>,
+}
+
+impl Invokables {
+    pub fn new() -> Self {
+        let mut new = Self {
+            registered: HashMap::new(),
+        };
+        // All invokables must be registered here
+        new.register::<edt::Read>();
+        new.register::<edt::Persist>();
+        new.register::<image::Render>();
+        new.register::<image::ReadMetadata>();
+        new.register::<json::Read>();
+        new.register::<json::Persist>();
+        new.register::<json::ListBackups>();
+        new.register::<json::RestoreBackup>();
+        new.register::<json::Search>();
+        new.register::<json::RenameIdentifier>();
+        new.register::<json::ListSchemas>();
+        new.register::<json::Diff>();
+        new.register::<json::BulkUpdate>();
+        new.register::<json::ExportCsv>();
+        new.register::<json::ImportCsv>();
+        new.register::<json::History>();
+        new.register::<json::Undo>();
+        new.register::<json::Redo>();
+        new.register::<json::CheckSchemaCompatibility>();
+        new.register::<json::Migrate>();
+        new.register::<json::Describe>();
+        new.register::<json::RenderSchemaReference>();
+        new.register::<l10n::Coverage>();
+        new.register::<mods::ListAvailable>();
+        new.register::<mods::ListEditable>();
+        new.register::<mods::ReadSelected>();
+        new.register::<mods::UpdateSelected>();
+        new.register::<mods::Create>();
+        new.register::<mods::Package>();
+        new.register::<mods::Install>();
+        new.register::<mods::ReadManifest>();
+        new.register::<mods::UpdateManifest>();
+        new.register::<mods::CloneMod>();
+        new.register::<mods::Rename>();
+        new.register::<mods::Delete>();
+        new.register::<mods::UpdateSettings>();
+        new.register::<mods::Conflicts>();
+        new.register::<mods::Lint>();
+        new.register::<mods::FindRedundant>();
+        new.register::<resources::List>();
+        new.register::<sounds::Read>();
+        new.register::<text::Read>();
+        new.register::<text::Persist>();
+        new.register::<toolset::ListInvokables>();
+        new.register::<toolset::ToolsetReadConfig>();
+        new.register::<toolset::ToolsetUpdateConfig>();
+
+        new
+    }
+
+    fn register<I: Invokable>(&mut self) {
+        let execute: Executable = Box::new(|state, value| {
+            let invalid_params = || {
+                ToolsetError::new(
+                    ErrorCode::InvalidParams,
+                    format!("invalid payload for invokable {}", I::name()),
+                )
+            };
+            let invokable: I = serde_json::from_value(value).with_context(invalid_params)?;
+
+            invokable.validate().with_context(invalid_params)?;
+
+            let result = invokable
+                .invoke(state)
+                .with_context(|| format!("failed to invoke invokable {}", I::name()))?;
+            let result = serde_json::to_value(result).with_context(|| {
+                format!("failed to serialize result for invokable {}", I::name())
+            })?;
+
+            Ok(result)
+        });
+        self.registered.insert(
+            I::name(),
+            Registration {
+                execute,
+                describe: InvokableDescription::of::<I>,
+            },
+        );
+    }
+
+    /// Returns the names of all registered invokables in alphabetical order
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.registered.keys().copied().collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Returns the JSON Schemas of all registered invokables in alphabetical order
+    pub fn describe(&self) -> Result<Vec<InvokableDescription>> {
+        self.names()
+            .into_iter()
+            .map(|name| {
+                (self.registered[name].describe)()
+                    .with_context(|| format!("failed to describe invokable {}", name))
+            })
+            .collect()
+    }
+
+    /// Runs the invokable named in the payload, failures are described by an `InvokeError`
+    pub fn invoke(&self, state: &AppState, payload: String) -> Result<String, InvokeError> {
+        self.try_invoke(state, payload).map_err(InvokeError::from)
+    }
+
+    fn try_invoke(&self, state: &AppState, payload: String) -> Result<String> {
+        let payload: InvokePayload = serde_json::from_str(&payload).with_context(|| {
+            ToolsetError::new(ErrorCode::InvalidParams, "failed to deserialize payload")
+        })?;
+
+        if let Some(registration) = self.registered.get(payload.func.as_str()) {
+            let result =
+                (registration.execute)(state, payload.params.clone()).with_context(|| {
+                    format!(
+                        "failed to invoke invokable {} with parameters {:?}",
+                        &payload.func, &payload.params
+                    )
+                })?;
+            let result =
+                serde_json::to_string(&result).context("failed to serialize invokable result")?;
+
+            Ok(result)
+        } else {
+            Err(ToolsetError::new(
+                ErrorCode::UnknownInvokable,
+                format!("invokable {} not found", payload.func.as_str()),
+            )
+            .into())
+        }
+    }
+}
+
+impl Default for Invokables {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,268 @@
+use crate::invokables::json::{apply_patch, diff_documents};
+use crate::invokables::Invokable;
+use crate::{files, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use stracciatella::{fs::resolve_existing_components, mods::ModPath, unicode::Nfc, vfs::Vfs};
+
+use super::PATCH_SUFFIX;
+
+/// Mods that changed a field, with the value they changed it to
+type FieldChanges = Vec<(String, Option<Value>)>;
+
+/// How the overrides of a JSON file by multiple mods relate to each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum JsonConflictKind {
+    /// All mods result in the same document
+    Identical,
+    /// The mods change different items and can be merged cleanly
+    DifferentItems,
+    /// The mods change different fields of the same items and can be merged cleanly
+    DifferentFields,
+    /// The mods change the same fields to different values
+    SameFields,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConflictingField {
+    /// Key of the item, see `json/diff`
+    key: String,
+    /// Dotted path of the field in the item, `*` for the item as a whole
+    field: String,
+    mods: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JsonConflict {
+    kind: JsonConflictKind,
+    /// Fields that more than one mod changes to different values
+    fields: Vec<ConflictingField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResourceConflict {
+    resource: String,
+    /// Mods that provide the resource, in the order they were requested
+    mods: Vec<String>,
+    /// Analysis of the overrides for JSON resources
+    json: Option<JsonConflict>,
+    /// Set if the JSON overrides could not be analyzed
+    error: Option<String>,
+}
+
+/// Reports resources that are provided by more than one of the given mods
+///
+/// A JSON patch counts as providing the file it patches.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Conflicts {
+    ids: Vec<String>,
+}
+
+impl Invokable for Conflicts {
+    type Output = Vec<ResourceConflict>;
+
+    fn name() -> &'static str {
+        "mods/conflicts"
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.ids.len() < 2 {
+            return Err(anyhow!("at least two mods are needed to find conflicts"));
+        }
+        let mut ids: Vec<_> = self.ids.iter().collect();
+        ids.sort();
+        if let Some(id) = ids.windows(2).find(|w| w[0] == w[1]).map(|w| w[0]) {
+            return Err(anyhow!("mod `{}` is listed more than once", id));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let config = state.try_config().context("failed to get config")?;
+        let mod_manager = state
+            .try_mod_manager()
+            .context("failed to get mod manager")?;
+
+        // Resources by lowercase path, engine lookups are case insensitive
+        let mut resources: BTreeMap<String, (String, Vec<(String, PathBuf)>)> = BTreeMap::new();
+        for id in &self.ids {
+            let m = mod_manager
+                .get_mod_by_id(id)
+                .ok_or_else(|| anyhow!("failed to find mod `{}`", id))?;
+            let ModPath::Path(mod_dir) = m.path();
+            let data_dir = resolve_existing_components(Path::new("data"), Some(mod_dir), true);
+            let mut provided = vec![];
+            for file in files::list_files_recursive(&data_dir)
+                .with_context(|| format!("failed to list files of mod `{}`", id))?
+            {
+                let file = files::to_resource_path(file);
+                let resource = match file.strip_suffix(PATCH_SUFFIX) {
+                    Some(base) => format!("{}.json", base),
+                    None => file,
+                };
+                let entry = resources
+                    .entry(resource.to_lowercase())
+                    .or_insert_with(|| (resource, vec![]));
+                if !provided.contains(&entry.0) {
+                    provided.push(entry.0.clone());
+                    entry.1.push((id.clone(), data_dir.clone()));
+                }
+            }
+        }
+
+        let mut vanilla = Vfs::new();
+        vanilla
+            .init(&config.to_engine_options(), mod_manager)
+            .map_err(|e| anyhow!("failed to initialize vfs: {}", e))?;
+
+        Ok(resources
+            .into_values()
+            .filter(|(_, providers)| providers.len() > 1)
+            .map(|(resource, providers)| {
+                let (json, error) = if resource.to_lowercase().ends_with(".json") {
+                    match json_conflict(&vanilla, &resource, &providers) {
+                        Ok(conflict) => (Some(conflict), None),
+                        Err(e) => (None, Some(format!("{:#}", e))),
+                    }
+                } else {
+                    (None, None)
+                };
+                ResourceConflict {
+                    resource,
+                    mods: providers.into_iter().map(|(id, _)| id).collect(),
+                    json,
+                    error,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Reads the document a mod results in for a JSON file, applying its patch if it has one
+fn read_mod_json(vanilla: &Option<Value>, data_dir: &Path, resource: &str) -> Result<Value> {
+    let read = |file: &str| -> Result<Option<String>> {
+        let path = resolve_existing_components(Path::new(file), Some(data_dir), true);
+        if !path.exists() {
+            return Ok(None);
+        }
+        read_to_string(&path)
+            .map(Some)
+            .with_context(|| format!("failed to read `{}`", file))
+    };
+    let patch_file = format!(
+        "{}{}",
+        &resource[..resource.len() - ".json".len()],
+        PATCH_SUFFIX
+    );
+
+    let mut value = match read(resource)? {
+        Some(json) => stracciatella::json::de::from_string(&json)
+            .map_err(|e| anyhow!("{}", e))
+            .with_context(|| format!("failed to parse `{}`", resource))?,
+        None => vanilla.clone().unwrap_or(Value::Null),
+    };
+    if let Some(json) = read(&patch_file)? {
+        let patch: Vec<Value> = stracciatella::json::de::from_string(&json)
+            .map_err(|e| anyhow!("{}", e))
+            .with_context(|| format!("failed to parse `{}`", patch_file))?;
+        apply_patch(&mut value, patch)
+            .with_context(|| format!("failed to apply `{}`", patch_file))?;
+    }
+    Ok(value)
+}
+
+fn json_conflict(
+    vfs: &Vfs,
+    resource: &str,
+    providers: &[(String, PathBuf)],
+) -> Result<JsonConflict> {
+    let vanilla = vfs.read_patched_json(&Nfc::caseless(resource)).ok();
+    let base = vanilla.clone().unwrap_or(Value::Null);
+
+    let mut values = vec![];
+    let mut changes: BTreeMap<(String, String), FieldChanges> = BTreeMap::new();
+    for (id, data_dir) in providers {
+        let value = read_mod_json(&vanilla, data_dir, resource)
+            .with_context(|| format!("failed to read mod `{}`", id))?;
+        for (field, new_value) in diff_documents(resource, &base, &value).changed_fields() {
+            changes
+                .entry(field)
+                .or_default()
+                .push((id.clone(), new_value));
+        }
+        values.push(value);
+    }
+
+    if values.windows(2).all(|w| w[0] == w[1]) {
+        return Ok(JsonConflict {
+            kind: JsonConflictKind::Identical,
+            fields: vec![],
+        });
+    }
+
+    // Changing a whole item conflicts with changing any of its fields
+    let mut by_item: BTreeMap<&str, Vec<(&str, &FieldChanges)>> = BTreeMap::new();
+    for ((key, field), changed_by) in &changes {
+        by_item.entry(key).or_default().push((field, changed_by));
+    }
+
+    let mut shared_items = false;
+    let mut conflicting = vec![];
+    for (key, fields) in by_item {
+        let mut mods_of_item: Vec<&str> = fields
+            .iter()
+            .flat_map(|(_, changed_by)| changed_by.iter().map(|(id, _)| id.as_str()))
+            .collect();
+        mods_of_item.sort_unstable();
+        mods_of_item.dedup();
+        if mods_of_item.len() < 2 {
+            continue;
+        }
+        shared_items = true;
+
+        let whole_item = fields.iter().find(|(field, _)| *field == "*");
+        for (field, changed_by) in &fields {
+            let differs = changed_by.windows(2).any(|w| w[0].1 != w[1].1);
+            let overridden_by_item = match whole_item {
+                Some((_, item_changed_by)) if *field != "*" => item_changed_by
+                    .iter()
+                    .any(|(id, _)| changed_by.iter().all(|(other, _)| other != id)),
+                _ => false,
+            };
+            if differs || overridden_by_item {
+                let mut mods: Vec<String> = changed_by.iter().map(|(id, _)| id.clone()).collect();
+                if overridden_by_item {
+                    if let Some((_, item_changed_by)) = whole_item {
+                        mods.extend(item_changed_by.iter().map(|(id, _)| id.clone()));
+                    }
+                }
+                mods.sort();
+                mods.dedup();
+                conflicting.push(ConflictingField {
+                    key: key.to_owned(),
+                    field: (*field).to_owned(),
+                    mods,
+                });
+            }
+        }
+    }
+
+    let kind = if !conflicting.is_empty() {
+        JsonConflictKind::SameFields
+    } else if shared_items {
+        JsonConflictKind::DifferentFields
+    } else {
+        JsonConflictKind::DifferentItems
+    };
+    Ok(JsonConflict {
+        kind,
+        fields: conflicting,
+    })
+}
@@ -0,0 +1,204 @@
+use crate::error::ToolsetError;
+use crate::invokables::Invokable;
+use crate::{files, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use stracciatella::mods::ModManager;
+
+use super::{mods_dir, validate_id, Manifest, Mod};
+
+/// Folder that macOS adds to archives, it is never part of a mod
+const MACOS_METADATA_DIR: &str = "__MACOSX";
+
+/// Installs a mod from a zip file into the `mods` folder of the stracciatella home
+///
+/// The archive needs to contain a single folder named like the mod id, containing the
+/// mod's `manifest.json`. This is the layout that `mod/package` produces.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Install {
+    path: PathBuf,
+}
+
+impl Invokable for Install {
+    type Output = Mod;
+
+    fn name() -> &'static str {
+        "mod/install"
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !self.path.is_absolute() {
+            return Err(anyhow!("archive path must be absolute"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let file = fs::File::open(&self.path).context("failed to open archive")?;
+        let mut archive = zip::ZipArchive::new(file).context("failed to read archive")?;
+        let (id, entries) = archive_entries(&mut archive)?;
+        validate_id(&id).with_context(|| format!("invalid mod id `{}`", id))?;
+        read_manifest(&mut archive, &entries).context("failed to read manifest")?;
+
+        let mut state = state.write();
+        match *state {
+            state::ToolsetState::Configured {
+                ref config,
+                ref mut mod_manager,
+                ..
+            } => {
+                let mods_dir = mods_dir(config);
+                let mod_dir = mods_dir.join(&id);
+                if mod_manager.get_mod_by_id(&id).is_some()
+                    || fs::exists(&mod_dir).context("failed to check mod directory existence")?
+                {
+                    return Err(anyhow!("mod with id {} already exists", id));
+                }
+
+                // Extract next to the final location, so a failed install leaves nothing behind
+                let temp_dir = mods_dir.join(format!(".{}.install", id));
+                let result = extract(&mut archive, &entries, &temp_dir).and_then(|_| {
+                    fs::rename(&temp_dir, &mod_dir).context("failed to move mod into place")
+                });
+                if let Err(e) = result {
+                    let _ = fs::remove_dir_all(&temp_dir);
+                    return Err(e);
+                }
+
+                // We need to reload the mods list to reflect the new mod
+                *mod_manager = ModManager::new(&config.to_engine_options())
+                    .context("failed to reinitialize mod manager")?;
+
+                let m = mod_manager
+                    .get_mod_by_id(&id)
+                    .ok_or_else(|| anyhow!("installed mod {} was not found", id))?;
+                Ok(Mod::from_stracciatella(m))
+            }
+            _ => Err(ToolsetError::not_configured().into()),
+        }
+    }
+}
+
+/// An entry of the archive that belongs to the mod
+struct Entry {
+    index: usize,
+    /// Path relative to the mod directory
+    path: PathBuf,
+    is_dir: bool,
+}
+
+/// Lists the entries of the archive and determines the id of the contained mod
+///
+/// Fails for entries that would be extracted outside of the target directory and for
+/// archives that do not contain exactly one mod folder.
+fn archive_entries<R: io::Read + io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> Result<(String, Vec<Entry>)> {
+    let mut root: Option<String> = None;
+    let mut entries = vec![];
+
+    for index in 0..archive.len() {
+        let file = archive
+            .by_index(index)
+            .context("failed to read archive entry")?;
+        let path = file
+            .enclosed_name()
+            .filter(|p| p.components().all(|c| matches!(c, Component::Normal(_))))
+            .ok_or_else(|| anyhow!("entry `{}` points outside of the archive", file.name()))?;
+        let mut components = path.components();
+        let Some(first) = components.next() else {
+            continue;
+        };
+        let first = first.as_os_str().to_string_lossy().into_owned();
+        if first == MACOS_METADATA_DIR {
+            continue;
+        }
+        let relative = components.as_path().to_owned();
+        if relative.as_os_str().is_empty() && !file.is_dir() {
+            return Err(anyhow!(
+                "archive must contain a single mod folder, found file `{}` at the top level",
+                first
+            ));
+        }
+        match &root {
+            Some(root) if *root != first => {
+                return Err(anyhow!(
+                    "archive must contain a single mod folder, found `{}` and `{}`",
+                    root,
+                    first
+                ));
+            }
+            Some(_) => {}
+            None => root = Some(first),
+        }
+        if !relative.as_os_str().is_empty() {
+            entries.push(Entry {
+                index,
+                path: relative,
+                is_dir: file.is_dir(),
+            });
+        }
+    }
+
+    let root = root.ok_or_else(|| anyhow!("archive is empty"))?;
+    Ok((root, entries))
+}
+
+fn read_manifest<R: io::Read + io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    entries: &[Entry],
+) -> Result<Manifest> {
+    let entry = entries
+        .iter()
+        .find(|e| !e.is_dir && e.path == Path::new("manifest.json"))
+        .ok_or_else(|| anyhow!("archive contains no `manifest.json` in the mod folder"))?;
+    let mut json = String::new();
+    io::Read::read_to_string(
+        &mut archive
+            .by_index(entry.index)
+            .context("failed to read archive entry")?,
+        &mut json,
+    )
+    .context("failed to read manifest")?;
+    let manifest: Manifest = stracciatella::json::de::from_string(&json)
+        .map_err(|e| anyhow!("{}", e))
+        .context("failed to parse manifest")?;
+    manifest.validate().context("invalid manifest")?;
+
+    Ok(manifest)
+}
+
+fn extract<R: io::Read + io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    entries: &[Entry],
+    dir: &Path,
+) -> Result<()> {
+    fs::create_dir_all(dir).context("failed to create mod dir")?;
+
+    for entry in entries {
+        let target = dir.join(&entry.path);
+        let display = files::to_resource_path(&entry.path);
+        if entry.is_dir {
+            fs::create_dir_all(&target)
+                .with_context(|| format!("failed to create `{}`", display))?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create parent of `{}`", display))?;
+        }
+        let mut source = archive
+            .by_index(entry.index)
+            .context("failed to read archive entry")?;
+        let mut destination =
+            fs::File::create(&target).with_context(|| format!("failed to create `{}`", display))?;
+        io::copy(&mut source, &mut destination)
+            .with_context(|| format!("failed to extract `{}`", display))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,366 @@
+use crate::config::ToolsetConfig;
+use crate::error::ToolsetError;
+use crate::invokables::Invokable;
+use crate::settings::ModSettings;
+use crate::{backups, dirs, files, journal, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, read_to_string};
+use std::path::{Path, PathBuf};
+use stracciatella::mods::{ModManager, ModPath};
+
+use super::{editable_mod_dir, mods_dir, validate_id, Manifest, Mod};
+
+fn ensure_id_available(
+    config: &ToolsetConfig,
+    mod_manager: &ModManager,
+    id: &str,
+) -> Result<PathBuf> {
+    let mod_dir = mods_dir(config).join(id);
+    if mod_manager.get_mod_by_id(id).is_some()
+        || fs::exists(&mod_dir).context("failed to check mod directory existence")?
+    {
+        return Err(anyhow!("mod with id {} already exists", id));
+    }
+    Ok(mod_dir)
+}
+
+/// Returns the directories and settings of all editable mods that require mod `id`
+fn dependent_mods(
+    config: &ToolsetConfig,
+    mod_manager: &ModManager,
+    id: &str,
+) -> Vec<(PathBuf, ModSettings)> {
+    mod_manager
+        .available_mods()
+        .iter()
+        .filter_map(|m| {
+            let ModPath::Path(dir) = m.path();
+            if m.id() == id || dir.starts_with(&config.stracciatella_install_dir) {
+                return None;
+            }
+            match ModSettings::read(dir) {
+                Ok(Some(settings)) if settings.required_mods.iter().any(|r| r == id) => {
+                    Some((dir.to_owned(), settings))
+                }
+                Ok(_) => None,
+                Err(e) => {
+                    log::warn!("skipping settings of mod `{}`: {:#}", m.id(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Copies a mod to a new id, e.g. to try out a variant of it
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneMod {
+    id: String,
+    new_id: String,
+    /// Name of the copy, defaults to the name of the original
+    name: Option<String>,
+}
+
+impl Invokable for CloneMod {
+    type Output = Mod;
+
+    fn name() -> &'static str {
+        "mod/clone"
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_id(&self.new_id).context("invalid new mod id")?;
+        if matches!(&self.name, Some(name) if name.trim().is_empty()) {
+            return Err(anyhow!("name must not be empty"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let mut state = state.write();
+
+        match *state {
+            state::ToolsetState::Configured {
+                ref config,
+                ref mut mod_manager,
+                ..
+            } => {
+                let source_dir = mod_manager
+                    .get_mod_by_id(&self.id)
+                    .map(|m| match m.path() {
+                        ModPath::Path(p) => p.to_owned(),
+                    })
+                    .ok_or_else(|| anyhow!("failed to find mod `{}`", self.id))?;
+                let mod_dir = ensure_id_available(config, mod_manager, &self.new_id)?;
+
+                if let Err(e) = files::copy_dir_recursive(&source_dir, &mod_dir)
+                    .and_then(|_| self.update_manifest(&mod_dir))
+                {
+                    let _ = fs::remove_dir_all(&mod_dir);
+                    return Err(e).context("failed to copy mod");
+                }
+
+                // We need to reload the mods list to reflect the new mod
+                *mod_manager = ModManager::new(&config.to_engine_options())
+                    .context("failed to reinitialize mod manager")?;
+
+                let m = mod_manager
+                    .get_mod_by_id(&self.new_id)
+                    .ok_or_else(|| anyhow!("cloned mod {} was not found", self.new_id))?;
+                Ok(Mod::from_stracciatella(m))
+            }
+            _ => Err(ToolsetError::not_configured().into()),
+        }
+    }
+}
+
+impl CloneMod {
+    fn update_manifest(&self, mod_dir: &Path) -> Result<()> {
+        let Some(name) = &self.name else {
+            return Ok(());
+        };
+        let manifest_path = mod_dir.join(Manifest::filename());
+        let json = read_to_string(&manifest_path).context("failed to read manifest")?;
+        let mut manifest: Manifest = stracciatella::json::de::from_string(&json)
+            .map_err(|e| anyhow!("{}", e))
+            .context("failed to parse manifest")?;
+        manifest.name = name.trim().to_owned();
+        files::write_atomic(
+            &manifest_path,
+            serde_json::to_string_pretty(&manifest).context("failed to serialize manifest")?,
+        )
+        .context("failed to write manifest")
+    }
+}
+
+/// Changes the id and with it the directory of a mod
+///
+/// Backups and the change history of the mod are moved along. Other mods requiring the
+/// mod and the last selected mod in the config are updated to the new id.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Rename {
+    id: String,
+    new_id: String,
+}
+
+impl Invokable for Rename {
+    type Output = Mod;
+
+    fn name() -> &'static str {
+        "mod/rename"
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_id(&self.new_id).context("invalid new mod id")
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let mut state = state.write();
+
+        match *state {
+            state::ToolsetState::Configured {
+                ref mut config,
+                ref mut mod_manager,
+                ref mut opened_mod,
+                ..
+            } => {
+                let source_dir = editable_mod_dir(config, mod_manager, &self.id)?;
+                let mod_dir = ensure_id_available(config, mod_manager, &self.new_id)?;
+                let dependents = dependent_mods(config, mod_manager, &self.id);
+
+                files::move_dir(&source_dir, &mod_dir).context("failed to move mod")?;
+                let result = self.move_history().and_then(|()| {
+                    let result = self.update_references(config, dependents);
+                    if result.is_err() {
+                        self.restore_history();
+                    }
+                    result
+                });
+                if result.is_err() {
+                    if let Err(e) = files::move_dir(&mod_dir, &source_dir) {
+                        log::error!("failed to move mod `{}` back: {:#}", self.id, e);
+                    }
+                }
+
+                // We need to reload the mods list in any case, the directory may have moved
+                *mod_manager = ModManager::new(&config.to_engine_options())
+                    .context("failed to reinitialize mod manager")?;
+
+                // The opened mod might be the renamed one or layered on top of it
+                if let Some(o) = opened_mod.take() {
+                    let renamed = mod_manager.get_mod_by_id(&self.new_id).is_some();
+                    let id = if o.m.id() == self.id && renamed {
+                        &self.new_id
+                    } else {
+                        o.m.id()
+                    };
+                    let mod_to_open = state::OpenedMod::new(config, mod_manager, id)
+                        .context("failed to initialize open mod")?;
+                    *opened_mod = Some(mod_to_open);
+                }
+                result?;
+
+                let m = mod_manager
+                    .get_mod_by_id(&self.new_id)
+                    .ok_or_else(|| anyhow!("renamed mod {} was not found", self.new_id))?;
+                Ok(Mod::from_stracciatella(m))
+            }
+            _ => Err(ToolsetError::not_configured().into()),
+        }
+    }
+}
+
+impl Rename {
+    /// Moves backups and journal to the new id, rolling back the backups if the journal fails
+    fn move_history(&self) -> Result<()> {
+        backups::rename_mod(&self.id, &self.new_id).context("failed to move backups")?;
+        if let Err(e) = journal::rename_mod(&self.id, &self.new_id) {
+            if let Err(e) = backups::rename_mod(&self.new_id, &self.id) {
+                log::error!("failed to move backups of `{}` back: {:#}", self.id, e);
+            }
+            return Err(e).context("failed to move journal");
+        }
+        Ok(())
+    }
+
+    /// Moves backups and journal back to the old id after a later step failed
+    fn restore_history(&self) {
+        if let Err(e) = backups::rename_mod(&self.new_id, &self.id) {
+            log::error!("failed to move backups of `{}` back: {:#}", self.id, e);
+        }
+        if let Err(e) = journal::rename_mod(&self.new_id, &self.id) {
+            log::error!("failed to move journal of `{}` back: {:#}", self.id, e);
+        }
+    }
+
+    /// Points required mods and the last selected mod at the new id
+    ///
+    /// If any of the changes fails, the settings that were already updated are restored.
+    fn update_references(
+        &self,
+        config: &mut ToolsetConfig,
+        dependents: Vec<(PathBuf, ModSettings)>,
+    ) -> Result<()> {
+        let mut updated = vec![];
+        for (dir, previous) in dependents {
+            let mut settings = previous.clone();
+            for required in settings.required_mods.iter_mut() {
+                if *required == self.id {
+                    *required = self.new_id.clone();
+                }
+            }
+            if let Err(e) = settings.write(&dir) {
+                restore_settings(updated);
+                return Err(e)
+                    .with_context(|| format!("failed to update settings in {}", dir.display()));
+            }
+            updated.push((dir, previous));
+        }
+        if config.last_selected_mod.as_deref() == Some(self.id.as_str()) {
+            config.last_selected_mod = Some(self.new_id.clone());
+            if let Err(e) = config.write() {
+                config.last_selected_mod = Some(self.id.clone());
+                restore_settings(updated);
+                return Err(e).context("failed to save config");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes back the settings of mods, logging failures
+fn restore_settings(settings: Vec<(PathBuf, ModSettings)>) {
+    for (dir, settings) in settings {
+        if let Err(e) = settings.write(&dir) {
+            log::error!("failed to restore settings in {}: {:#}", dir.display(), e);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedMod {
+    /// Directory the mod was moved to
+    trash_path: PathBuf,
+}
+
+/// Deletes a mod by moving it to the toolset's trash folder
+///
+/// Backups and the change history of the mod are moved to the trash next to it. If the mod
+/// is currently opened, it is closed, any other opened mod is reopened without it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Delete {
+    id: String,
+}
+
+impl Invokable for Delete {
+    type Output = DeletedMod;
+
+    fn name() -> &'static str {
+        "mod/delete"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let mut state = state.write();
+
+        match *state {
+            state::ToolsetState::Configured {
+                ref mut config,
+                ref mut mod_manager,
+                ref mut opened_mod,
+                ..
+            } => {
+                let mod_dir = editable_mod_dir(config, mod_manager, &self.id)?;
+                let trash_dir = dirs::project_dirs()?.data_dir().join("trash");
+                let trash_name = format!("{}-{}", self.id, backups::now_millis()?);
+                let trash_path = trash_dir.join(&trash_name);
+
+                files::move_dir(&mod_dir, &trash_path).context("failed to move mod to trash")?;
+
+                if matches!(opened_mod, Some(o) if o.m.id() == self.id) {
+                    *opened_mod = None;
+                }
+
+                // The mod is gone, so the state is updated even if moving its history fails
+                let moved =
+                    backups::move_mod(&self.id, &trash_dir.join(format!("{}-backups", trash_name)))
+                        .context("failed to move backups to trash")
+                        .and_then(|_| {
+                            journal::move_mod(
+                                &self.id,
+                                &trash_dir.join(format!("{}-journal.json", trash_name)),
+                            )
+                            .context("failed to move journal to trash")
+                        });
+                let forgotten = if config.last_selected_mod.as_deref() == Some(self.id.as_str()) {
+                    config.last_selected_mod = None;
+                    config.write().context("failed to save config")
+                } else {
+                    Ok(())
+                };
+
+                // We need to reload the mods list to reflect the removed mod
+                *mod_manager = ModManager::new(&config.to_engine_options())
+                    .context("failed to reinitialize mod manager")?;
+
+                // The opened mod might be layered on top of the deleted one
+                let reopened = match opened_mod.take() {
+                    Some(o) => state::OpenedMod::new(config, mod_manager, o.m.id())
+                        .map(|o| *opened_mod = Some(o))
+                        .context("failed to initialize open mod"),
+                    None => Ok(()),
+                };
+                moved?;
+                forgotten?;
+                reopened?;
+
+                Ok(DeletedMod { trash_path })
+            }
+            _ => Err(ToolsetError::not_configured().into()),
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use crate::invokables::json::{check_mod_json, IssueSource};
+use crate::invokables::Invokable;
+use crate::settings::ModSettings;
+use crate::{files, l10n, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, read_to_string};
+use std::path::{Path, PathBuf};
+use stracciatella::mods::ModPath;
+
+use super::{identical_to_vanilla, validate_id, Manifest, PATCH_SUFFIX};
+
+/// Extensions of files the engine reads from the data folder
+const KNOWN_EXTENSIONS: &[&str] = &[
+    "ani", "bin", "dat", "edt", "gap", "jsd", "json", "lua", "mp3", "npc", "ogg", "pcx", "slf",
+    "smk", "sti", "txt", "wav",
+];
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    /// The mod is broken, e.g. the engine fails to load it
+    Error,
+    /// The mod works, but probably not as intended
+    Warning,
+    /// Something that might be worth cleaning up
+    Info,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LintCheck {
+    Manifest,
+    UnknownFile,
+    CaseCollision,
+    Schema,
+    Patch,
+    EmptyDirectory,
+    IdenticalToVanilla,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LintFinding {
+    severity: Severity,
+    check: LintCheck,
+    /// Path relative to the mod directory
+    file: Option<String>,
+    /// JSON pointer into the file
+    pointer: Option<String>,
+    message: String,
+}
+
+impl LintFinding {
+    fn new(severity: Severity, check: LintCheck, file: Option<&str>, message: String) -> Self {
+        LintFinding {
+            severity,
+            check,
+            file: file.map(|f| f.to_owned()),
+            pointer: None,
+            message,
+        }
+    }
+}
+
+/// Runs health checks on the selected mod, e.g. before releasing it
+///
+/// Findings are sorted by severity, errors first.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Lint;
+
+impl Invokable for Lint {
+    type Output = Vec<LintFinding>;
+
+    fn name() -> &'static str {
+        "mod/lint"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let schema_manager = state
+            .try_schema_manager()
+            .context("failed to get schema manager")?;
+        let ModPath::Path(mod_dir) = selected_mod.m.path();
+        let data_dir = selected_mod.data_path("");
+        let data_files: Vec<String> = files::list_files_recursive(&data_dir)
+            .context("failed to list mod files")?
+            .into_iter()
+            .map(files::to_resource_path)
+            .collect();
+        let mut findings = vec![];
+
+        check_manifest(selected_mod.m.id(), mod_dir, &mut findings);
+        check_unknown_files(mod_dir, &data_files, &mut findings)?;
+        check_case_collisions(&data_files, &mut findings);
+
+        for file in check_mod_json(selected_mod, schema_manager)? {
+            // Files without schema are still read if something references them
+            let has_schema = schema_manager
+                .get(Path::new(&l10n::base_resource(&file.file)))
+                .is_some();
+            for issue in file.issues {
+                let (severity, check) = match issue.source {
+                    IssueSource::File if !has_schema => (Severity::Warning, LintCheck::UnknownFile),
+                    IssueSource::File | IssueSource::Value => (Severity::Error, LintCheck::Schema),
+                    IssueSource::Patch => (Severity::Error, LintCheck::Patch),
+                };
+                findings.push(LintFinding {
+                    severity,
+                    check,
+                    file: Some(format!("data/{}", file.file)),
+                    pointer: Some(issue.pointer).filter(|p| !p.is_empty()),
+                    message: issue.message,
+                });
+            }
+        }
+
+        for dir in empty_dirs(&data_dir).context("failed to list mod directories")? {
+            findings.push(LintFinding::new(
+                Severity::Info,
+                LintCheck::EmptyDirectory,
+                Some(&format!("data/{}", files::to_resource_path(dir))),
+                "directory is empty".to_owned(),
+            ));
+        }
+
+        for file in data_files.iter().filter(|f| !f.ends_with(PATCH_SUFFIX)) {
+            // Unparseable JSON is already reported by the schema check
+            if identical_to_vanilla(selected_mod, file).unwrap_or(false) {
+                findings.push(LintFinding::new(
+                    Severity::Info,
+                    LintCheck::IdenticalToVanilla,
+                    Some(&format!("data/{}", file)),
+                    "file is identical to the game's file and can be removed".to_owned(),
+                ));
+            }
+        }
+
+        findings.sort_by(|a, b| (a.severity, &a.file).cmp(&(b.severity, &b.file)));
+        Ok(findings)
+    }
+}
+
+fn check_manifest(id: &str, mod_dir: &Path, findings: &mut Vec<LintFinding>) {
+    let file = Some(Manifest::filename());
+    let mut error = |message: String| {
+        findings.push(LintFinding::new(
+            Severity::Error,
+            LintCheck::Manifest,
+            file,
+            message,
+        ))
+    };
+    let manifest = read_to_string(mod_dir.join(Manifest::filename()))
+        .context("failed to read manifest")
+        .and_then(|json| {
+            stracciatella::json::de::from_string::<Manifest>(&json)
+                .map_err(|e| anyhow!("{}", e))
+                .context("failed to parse manifest")
+        });
+    match manifest {
+        Ok(manifest) => {
+            if let Err(e) = manifest.validate() {
+                error(format!("{:#}", e));
+            }
+        }
+        Err(e) => error(format!("{:#}", e)),
+    }
+    if let Err(e) = validate_id(id) {
+        findings.push(LintFinding::new(
+            Severity::Warning,
+            LintCheck::Manifest,
+            None,
+            format!("mod directory `{}` is not a valid mod id: {:#}", id, e),
+        ));
+    }
+}
+
+fn check_unknown_files(
+    mod_dir: &Path,
+    data_files: &[String],
+    findings: &mut Vec<LintFinding>,
+) -> Result<()> {
+    for entry in fs::read_dir(mod_dir).context("failed to read mod directory")? {
+        let name = entry
+            .context("failed to read mod directory entry")?
+            .file_name()
+            .to_string_lossy()
+            .into_owned();
+        if name != Manifest::filename() && name != ModSettings::filename() && name != "data" {
+            findings.push(LintFinding::new(
+                Severity::Info,
+                LintCheck::UnknownFile,
+                Some(&name),
+                "outside of the data directory, the engine does not load it".to_owned(),
+            ));
+        }
+    }
+
+    for file in data_files {
+        let extension = Path::new(file)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase());
+        match extension {
+            Some(e) if KNOWN_EXTENSIONS.contains(&e.as_str()) => {}
+            _ => findings.push(LintFinding::new(
+                Severity::Warning,
+                LintCheck::UnknownFile,
+                Some(&format!("data/{}", file)),
+                "the engine does not load files of this type".to_owned(),
+            )),
+        }
+    }
+    Ok(())
+}
+
+/// Reports files whose paths only differ in case, only one of them is used by the engine
+fn check_case_collisions(data_files: &[String], findings: &mut Vec<LintFinding>) {
+    let mut by_lowercase: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for file in data_files {
+        by_lowercase
+            .entry(file.to_lowercase())
+            .or_default()
+            .push(file);
+    }
+    for colliding in by_lowercase.into_values().filter(|f| f.len() > 1) {
+        for file in &colliding {
+            findings.push(LintFinding::new(
+                Severity::Error,
+                LintCheck::CaseCollision,
+                Some(&format!("data/{}", file)),
+                format!(
+                    "paths only differ in case: {}",
+                    colliding
+                        .iter()
+                        .map(|f| format!("`{}`", f))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
+        }
+    }
+}
+
+/// Lists directories below `dir` that contain no entries, relative to `dir`
+fn empty_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut result = vec![];
+    let mut pending = vec![PathBuf::new()];
+
+    while let Some(relative_dir) = pending.pop() {
+        let mut is_empty = true;
+        for entry in fs::read_dir(dir.join(&relative_dir)).into_iter().flatten() {
+            let entry = entry.context("failed to read dir entry")?;
+            is_empty = false;
+            if entry.path().is_dir() {
+                pending.push(relative_dir.join(entry.file_name()));
+            }
+        }
+        if is_empty && !relative_dir.as_os_str().is_empty() {
+            result.push(relative_dir);
+        }
+    }
+    result.sort();
+
+    Ok(result)
+}
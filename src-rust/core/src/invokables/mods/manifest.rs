@@ -0,0 +1,125 @@
+use crate::error::ToolsetError;
+use crate::invokables::Invokable;
+use crate::{files, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs::read_to_string;
+use std::path::Path;
+use stracciatella::mods::{ModManager, ModPath};
+
+use super::{Manifest, Mod};
+
+fn read_manifest(manifest_path: &Path) -> Result<Manifest> {
+    let json = read_to_string(manifest_path).context("failed to read manifest")?;
+    stracciatella::json::de::from_string(&json)
+        .map_err(|e| anyhow!("{}", e))
+        .context("failed to parse manifest")
+}
+
+/// Reads the whole `manifest.json` of the selected mod, including fields the toolset does not
+/// edit by name
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReadManifest;
+
+impl Invokable for ReadManifest {
+    type Output = Manifest;
+
+    fn name() -> &'static str {
+        "mod/readManifest"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let ModPath::Path(mod_dir) = selected_mod.m.path();
+
+        read_manifest(&mod_dir.join(Manifest::filename()))
+    }
+}
+
+/// Updates the `manifest.json` of the selected mod
+///
+/// Fields of the manifest that are not part of the request are kept as they are.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateManifest {
+    name: String,
+    version: String,
+    description: Option<String>,
+    /// Further fields to set, e.g. ones a newer engine understands, `null` removes a field
+    fields: Option<Map<String, Value>>,
+}
+
+impl Invokable for UpdateManifest {
+    type Output = Mod;
+
+    fn name() -> &'static str {
+        "mod/updateManifest"
+    }
+
+    fn validate(&self) -> Result<()> {
+        Manifest {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            description: self.description.clone(),
+            other: self.fields.clone().unwrap_or_default(),
+        }
+        .validate()
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let mut state = state.write();
+
+        match *state {
+            state::ToolsetState::Configured {
+                ref config,
+                ref mut mod_manager,
+                ref mut opened_mod,
+                ..
+            } => {
+                let selected_mod = opened_mod
+                    .as_ref()
+                    .ok_or_else(ToolsetError::no_mod_selected)?;
+                let id = selected_mod.m.id().to_owned();
+                let ModPath::Path(mod_dir) = selected_mod.m.path();
+                let manifest_path = mod_dir.join(Manifest::filename());
+
+                let mut manifest = read_manifest(&manifest_path)?;
+                manifest.name = self.name.trim().to_owned();
+                manifest.version = self.version.trim().to_owned();
+                manifest.description = self
+                    .description
+                    .as_ref()
+                    .map(|d| d.trim().to_owned())
+                    .filter(|d| !d.is_empty());
+                for (key, value) in self.fields.iter().flatten() {
+                    if value.is_null() {
+                        manifest.other.remove(key);
+                    } else {
+                        manifest.other.insert(key.clone(), value.clone());
+                    }
+                }
+                files::write_atomic(
+                    &manifest_path,
+                    serde_json::to_string_pretty(&manifest)
+                        .context("failed to serialize manifest")?,
+                )
+                .context("failed to write manifest")?;
+
+                // We need to reload the mods list to reflect the new manifest
+                *mod_manager = ModManager::new(&config.to_engine_options())
+                    .context("failed to reinitialize mod manager")?;
+
+                let mod_to_open = state::OpenedMod::new(config, mod_manager, &id)
+                    .context("failed to initialize open mod")?;
+                let m = Mod::from_stracciatella(&mod_to_open.m);
+                *opened_mod = Some(mod_to_open);
+                Ok(m)
+            }
+            _ => Err(ToolsetError::not_configured().into()),
+        }
+    }
+}
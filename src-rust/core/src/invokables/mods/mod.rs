@@ -1,16 +1,152 @@
+use crate::config::ToolsetConfig;
+use crate::error::ToolsetError;
 use crate::invokables::Invokable;
+use crate::settings::ModSettings;
 use crate::state;
 use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::fs::read_to_string;
+use std::io::Read as _;
 use std::path::{Path, PathBuf};
 use stracciatella::mods::ModManager;
 use stracciatella::{fs::resolve_existing_components, mods::ModPath};
+use stracciatella::{unicode::Nfc, vfs::VfsLayer};
+
+mod conflicts;
+mod install;
+mod lifecycle;
+mod lint;
+mod manifest;
+mod package;
+mod redundant;
+mod settings;
+
+pub use conflicts::Conflicts;
+pub use install::Install;
+pub use lifecycle::{CloneMod, Delete, Rename};
+pub use lint::Lint;
+pub use manifest::{ReadManifest, UpdateManifest};
+pub use package::Package;
+pub use redundant::FindRedundant;
+pub use settings::UpdateSettings;
+
+/// Suffix of files that patch a JSON file instead of replacing it
+const PATCH_SUFFIX: &str = ".patch.json";
+
+/// Returns whether a file of the selected mod has the same content as the file beneath it
+///
+/// JSON files are compared by value, so formatting differences are ignored. Returns `false`
+/// for files that do not exist beneath the mod.
+fn identical_to_vanilla(selected_mod: &state::OpenedMod, file: &str) -> Result<bool> {
+    let bytes = std::fs::read(selected_mod.data_path(file)).context("failed to read mod file")?;
+    if file.to_lowercase().ends_with(".json") {
+        let Ok(vanilla) = selected_mod.vfs.read_patched_json(&Nfc::caseless(file)) else {
+            return Ok(false);
+        };
+        let value: serde_json::Value =
+            stracciatella::json::de::from_string(&String::from_utf8_lossy(&bytes))
+                .map_err(|e| anyhow!("{}", e))
+                .context("failed to parse mod file")?;
+        return Ok(value == vanilla);
+    }
+    let Ok(mut vanilla) = selected_mod.vfs.open(&Nfc::caseless(file)) else {
+        return Ok(false);
+    };
+    let mut vanilla_bytes = vec![];
+    vanilla
+        .read_to_end(&mut vanilla_bytes)
+        .context("failed to read vanilla file")?;
+    Ok(bytes == vanilla_bytes)
+}
+
+/// Returns the directory that mods are installed into
+fn mods_dir(config: &ToolsetConfig) -> PathBuf {
+    resolve_existing_components(
+        Path::new("mods"),
+        Some(Path::new(&config.stracciatella_home)),
+        true,
+    )
+}
+
+/// Returns the directory of a mod that the toolset may modify
+fn editable_mod_dir(config: &ToolsetConfig, mod_manager: &ModManager, id: &str) -> Result<PathBuf> {
+    let m = mod_manager
+        .get_mod_by_id(id)
+        .ok_or_else(|| anyhow!("failed to find mod `{}`", id))?;
+    let ModPath::Path(dir) = m.path();
+    if dir.starts_with(&config.stracciatella_install_dir) {
+        return Err(anyhow!(
+            "mod `{}` is shipped with the game and cannot be changed",
+            id
+        ));
+    }
+    Ok(dir.to_owned())
+}
+
+/// Validates a mod id, it is used as the directory name of the mod
+fn validate_id(id: &str) -> Result<()> {
+    if id.is_empty() {
+        return Err(anyhow!("mod id must not be empty"));
+    }
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(anyhow!(
+            "mod id must contain only lowercase letters, numbers and dashes"
+        ));
+    }
+    Ok(())
+}
+
+/// A mod's `manifest.json`, fields the toolset does not know about are preserved
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Manifest {
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(flatten)]
+    other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Manifest {
+    fn filename() -> &'static str {
+        "manifest.json"
+    }
+
+    /// Fields with a dedicated property, which cannot be set as another field
+    const KNOWN_FIELDS: [&'static str; 3] = ["name", "version", "description"];
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+    fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(anyhow!("name must not be empty"));
+        }
+        if self.version.trim().is_empty() {
+            return Err(anyhow!("version must not be empty"));
+        }
+        if self.name.contains('\n') || self.version.contains(char::is_whitespace) {
+            return Err(anyhow!(
+                "name must be a single line and version must not contain whitespace"
+            ));
+        }
+        for key in self.other.keys() {
+            if key.trim().is_empty() {
+                return Err(anyhow!("field names must not be empty"));
+            }
+            if Self::KNOWN_FIELDS.contains(&key.as_str()) {
+                return Err(anyhow!("`{}` must not be set as another field", key));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Mod {
     id: String,
     name: String,
+    #[serde(default)]
     description: String,
     version: String,
     path: PathBuf,
@@ -30,25 +166,17 @@ impl Mod {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
-pub struct ModSettings {
-    required_mods: Vec<String>,
-}
-
-impl ModSettings {
-    fn filename() -> &'static str {
-        "toolset.json"
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct EditableMod {
     #[serde(flatten)]
     m: Mod,
     settings: Option<ModSettings>,
+    /// Why the settings could not be read or resolved, e.g. a required mod is missing
+    settings_error: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ListAvailable;
 
 impl Invokable for ListAvailable {
@@ -71,7 +199,7 @@ impl Invokable for ListAvailable {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ListEditable;
 
 impl Invokable for ListEditable {
@@ -87,23 +215,26 @@ impl Invokable for ListEditable {
             .context("failed to get available mods")?;
         let state = state.read();
         let config = state.try_config().context("failed to get config")?;
+        let mod_manager = state
+            .try_mod_manager()
+            .context("failed to get mod manager")?;
         let mut editable_mods = vec![];
 
         for m in available_mods {
             if m.path.starts_with(&config.stracciatella_install_dir) {
                 continue;
             }
-            let toolset_file = resolve_existing_components(
-                Path::new(ModSettings::filename()),
-                Some(&m.path),
-                true,
-            );
-            let settings = read_to_string(&toolset_file)
-                .ok()
-                .and_then(|s| serde_json::from_str(&s).ok());
+            let (settings, settings_error) = match ModSettings::read(&m.path) {
+                Ok(settings) => {
+                    let error = crate::settings::required_mods(mod_manager, &m.id).err();
+                    (settings, error)
+                }
+                Err(e) => (None, Some(e)),
+            };
             editable_mods.push(EditableMod {
                 m: m.clone(),
                 settings,
+                settings_error: settings_error.map(|e| format!("{:#}", e)),
             });
         }
 
@@ -111,7 +242,7 @@ impl Invokable for ListEditable {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReadSelected;
 
 impl Invokable for ReadSelected {
@@ -130,7 +261,7 @@ impl Invokable for ReadSelected {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UpdateSelected(EditableMod);
 
 impl Invokable for UpdateSelected {
@@ -156,12 +287,12 @@ impl Invokable for UpdateSelected {
                 *opened_mod = Some(mod_to_open);
                 Ok(m)
             }
-            _ => Err(anyhow!("toolset state not configured")),
+            _ => Err(ToolsetError::not_configured().into()),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Create {
     pub id: String,
     pub name: String,
@@ -186,12 +317,7 @@ impl Invokable for Create {
                 ref mut opened_mod,
                 ..
             } => {
-                let mods_dir = resolve_existing_components(
-                    Path::new("mods"),
-                    Some(&Path::new(&config.stracciatella_home)),
-                    true,
-                );
-                let mod_dir = mods_dir.join(&self.id);
+                let mod_dir = mods_dir(config).join(&self.id);
                 let data_dir = mod_dir.join("data");
                 let manifest_path = mod_dir.join("manifest.json");
 
@@ -237,7 +363,7 @@ impl Invokable for Create {
                 *opened_mod = Some(mod_to_open);
                 Ok(m)
             }
-            _ => Err(anyhow!("toolset state not configured")),
+            _ => Err(ToolsetError::not_configured().into()),
         }
     }
 }
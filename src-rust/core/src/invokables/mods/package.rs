@@ -0,0 +1,164 @@
+use crate::invokables::Invokable;
+use crate::{files, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use stracciatella::mods::ModPath;
+use zip::write::SimpleFileOptions;
+
+use super::ModSettings;
+
+/// Returns whether a file of the mod directory must not end up in a package
+fn is_excluded(relative: &Path) -> bool {
+    let Some(name) = relative.file_name().map(|n| n.to_string_lossy()) else {
+        return true;
+    };
+    // Hidden files include files that are currently being written
+    relative == Path::new(ModSettings::filename())
+        || name.starts_with('.')
+        || name.ends_with('~')
+        || name.to_lowercase().ends_with(".bak")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageResult {
+    path: PathBuf,
+    checksum_path: PathBuf,
+    sha256: String,
+    files: usize,
+}
+
+/// Packages the selected mod into a zip file that can be extracted into the `mods` folder
+///
+/// The archive contains a single folder named like the mod id with the manifest, the data
+/// folder and any extra files or folders of the mod directory that were requested.
+/// A `.sha256` checksum file is written next to the archive.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Package {
+    path: PathBuf,
+    extras: Option<Vec<String>>,
+}
+
+impl Invokable for Package {
+    type Output = PackageResult;
+
+    fn name() -> &'static str {
+        "mod/package"
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !self.path.is_absolute() {
+            return Err(anyhow!("package path must be absolute"));
+        }
+        if self.path.extension().and_then(|e| e.to_str()) != Some("zip") {
+            return Err(anyhow!("package path must end with `.zip`"));
+        }
+        for extra in self.extras.iter().flatten() {
+            if extra.is_empty() || extra.contains("..") || Path::new(extra).is_absolute() {
+                return Err(anyhow!("invalid extra path `{}`", extra));
+            }
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let ModPath::Path(mod_dir) = selected_mod.m.path();
+
+        let mut entries = vec![PathBuf::from("manifest.json")];
+        include_dir(mod_dir, Path::new("data"), &mut entries)?;
+        for extra in self.extras.iter().flatten() {
+            let extra_path = mod_dir.join(extra);
+            if extra_path.is_dir() {
+                include_dir(mod_dir, Path::new(extra), &mut entries)?;
+            } else if extra_path.is_file() {
+                entries.push(PathBuf::from(extra));
+            } else {
+                return Err(anyhow!("extra `{}` does not exist in the mod", extra));
+            }
+        }
+        entries.retain(|e| !is_excluded(e));
+        entries.sort();
+        entries.dedup();
+
+        if !mod_dir.join("manifest.json").is_file() {
+            return Err(anyhow!("mod has no `manifest.json`"));
+        }
+
+        let temp_path = self.path.with_extension("zip.tmp");
+        let result = write_zip(&temp_path, mod_dir, selected_mod.m.id(), &entries)
+            .and_then(|_| fs::rename(&temp_path, &self.path).context("failed to move package"));
+        if let Err(e) = result {
+            let _ = files::remove_file(&temp_path);
+            return Err(e);
+        }
+
+        let sha256 = sha256_file(&self.path).context("failed to compute checksum")?;
+        let file_name = self
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let checksum_path = self.path.with_extension("zip.sha256");
+        files::write_atomic(&checksum_path, format!("{}  {}\n", sha256, file_name))
+            .context("failed to write checksum file")?;
+
+        Ok(PackageResult {
+            path: self.path.clone(),
+            checksum_path,
+            sha256,
+            files: entries.len(),
+        })
+    }
+}
+
+/// Adds all files below `dir` of the mod directory, relative to the mod directory
+fn include_dir(mod_dir: &Path, dir: &Path, entries: &mut Vec<PathBuf>) -> Result<()> {
+    for file in files::list_files_recursive(mod_dir.join(dir))
+        .with_context(|| format!("failed to list files in `{}`", dir.display()))?
+    {
+        entries.push(dir.join(file));
+    }
+    Ok(())
+}
+
+fn write_zip(path: &Path, mod_dir: &Path, root: &str, entries: &[PathBuf]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("failed to create package directory")?;
+    }
+    let file = fs::File::create(path).context("failed to create package")?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for entry in entries {
+        let name = format!("{}/{}", root, files::to_resource_path(entry));
+        zip.start_file(name.as_str(), options)
+            .with_context(|| format!("failed to add `{}` to package", name))?;
+        let mut source = fs::File::open(mod_dir.join(entry))
+            .with_context(|| format!("failed to open `{}`", entry.display()))?;
+        io::copy(&mut source, &mut zip)
+            .with_context(|| format!("failed to add `{}` to package", name))?;
+    }
+
+    zip.finish()
+        .context("failed to finish package")?
+        .flush()
+        .context("failed to write package")?;
+    Ok(())
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path).context("failed to open package")?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).context("failed to read package")?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
@@ -0,0 +1,130 @@
+use crate::invokables::json::apply_patch;
+use crate::invokables::Invokable;
+use crate::{backups, dirs, files, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, read_to_string};
+use std::path::{Path, PathBuf};
+use stracciatella::unicode::Nfc;
+
+use super::{identical_to_vanilla, PATCH_SUFFIX};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RedundantFiles {
+    /// Files in the data directory of the mod that change nothing
+    files: Vec<String>,
+    /// Directory the files were moved to, if they were deleted
+    trash_path: Option<PathBuf>,
+}
+
+/// Finds files of the selected mod that are identical to the files beneath the mod
+///
+/// Binary files are compared byte by byte, JSON files by value. Patches are redundant if
+/// applying them changes nothing. With `delete`, the redundant files are moved to the
+/// toolset's trash folder.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FindRedundant {
+    delete: bool,
+}
+
+impl Invokable for FindRedundant {
+    type Output = RedundantFiles;
+
+    fn name() -> &'static str {
+        "mod/findRedundant"
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let data_dir = selected_mod.data_path("");
+
+        let mut redundant = vec![];
+        for file in files::list_files_recursive(&data_dir).context("failed to list mod files")? {
+            let file = files::to_resource_path(file);
+            let is_redundant = match file.strip_suffix(PATCH_SUFFIX) {
+                Some(base) => patch_is_noop(selected_mod, &format!("{}.json", base), &file),
+                None => identical_to_vanilla(selected_mod, &file),
+            };
+            // Files that cannot be compared are never redundant, `mod/lint` reports them
+            if is_redundant.unwrap_or(false) {
+                redundant.push(file);
+            }
+        }
+
+        let trash_path = if self.delete && !redundant.is_empty() {
+            let trash_path = dirs::project_dirs()?.data_dir().join("trash").join(format!(
+                "{}-redundant-{}",
+                selected_mod.m.id(),
+                backups::now_millis()?
+            ));
+            for file in &redundant {
+                move_to_trash(&data_dir, &trash_path.join("data"), file)
+                    .with_context(|| format!("failed to delete `{}`", file))?;
+            }
+            Some(trash_path)
+        } else {
+            None
+        };
+
+        Ok(RedundantFiles {
+            files: redundant,
+            trash_path,
+        })
+    }
+}
+
+/// Returns whether applying a patch leaves the document it patches unchanged
+fn patch_is_noop(selected_mod: &state::OpenedMod, file: &str, patch_file: &str) -> Result<bool> {
+    let read = |file: &str| -> Result<Value> {
+        let json = read_to_string(selected_mod.data_path(file)).context("failed to read file")?;
+        stracciatella::json::de::from_string(&json)
+            .map_err(|e| anyhow!("{}", e))
+            .context("failed to parse file")
+    };
+
+    let original = if selected_mod.data_path(file).exists() {
+        read(file)?
+    } else {
+        selected_mod
+            .vfs
+            .read_patched_json(&Nfc::caseless(file))
+            .context("failed to read vanilla json")?
+    };
+    let patch = match read(patch_file)? {
+        Value::Array(patch) => patch,
+        _ => return Err(anyhow!("patch must be an array")),
+    };
+    let mut patched = original.clone();
+    apply_patch(&mut patched, patch)?;
+
+    Ok(patched == original)
+}
+
+/// Moves a file to the trash and removes directories that are left empty
+fn move_to_trash(data_dir: &Path, trash_dir: &Path, file: &str) -> Result<()> {
+    let source = data_dir.join(file);
+    let target = trash_dir.join(file);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).context("failed to create trash dir")?;
+    }
+    if fs::rename(&source, &target).is_err() {
+        fs::copy(&source, &target).context("failed to copy file to trash")?;
+        files::remove_file(&source).context("failed to remove file")?;
+    }
+
+    let mut dir = source.parent();
+    while let Some(d) = dir.filter(|d| *d != data_dir) {
+        // Fails for directories that are not empty
+        if fs::remove_dir(d).is_err() {
+            break;
+        }
+        dir = d.parent();
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::error::ToolsetError;
+use crate::invokables::Invokable;
+use crate::state;
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{editable_mod_dir, ModSettings};
+
+/// Updates the `toolset.json` of a mod and reopens the opened mod with the new settings
+///
+/// The mod does not need to be opened, so settings that keep a mod from opening can be
+/// fixed. Fails without writing anything if a required mod is missing or requirements are
+/// circular.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateSettings {
+    /// Mod to update, defaults to the selected mod
+    id: Option<String>,
+    #[serde(flatten)]
+    settings: ModSettings,
+}
+
+impl Invokable for UpdateSettings {
+    type Output = ModSettings;
+
+    fn name() -> &'static str {
+        "mod/updateSettings"
+    }
+
+    fn validate(&self) -> Result<()> {
+        let required_mods = &self.settings.required_mods;
+        for (index, id) in required_mods.iter().enumerate() {
+            if id.is_empty() {
+                return Err(anyhow!("required mod ids must not be empty"));
+            }
+            if required_mods[..index].contains(id) {
+                return Err(anyhow!("mod `{}` is required more than once", id));
+            }
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let mut state = state.write();
+
+        match *state {
+            state::ToolsetState::Configured {
+                ref config,
+                ref mod_manager,
+                ref mut opened_mod,
+                ..
+            } => {
+                let id = match &self.id {
+                    Some(id) => id.clone(),
+                    None => opened_mod
+                        .as_ref()
+                        .ok_or_else(ToolsetError::no_mod_selected)?
+                        .m
+                        .id()
+                        .to_owned(),
+                };
+                let mod_dir = editable_mod_dir(config, mod_manager, &id)?;
+
+                self.settings
+                    .resolve_required_mods(mod_manager, &id)
+                    .context("invalid required mods")?;
+                self.settings.write(&mod_dir)?;
+
+                // The opened mod might be the updated one or layered on top of it
+                if let Some(opened_id) = opened_mod.as_ref().map(|o| o.m.id().to_owned()) {
+                    let mod_to_open = state::OpenedMod::new(config, mod_manager, &opened_id)
+                        .context("failed to initialize open mod")?;
+                    *opened_mod = Some(mod_to_open);
+                }
+                Ok(self.settings.clone())
+            }
+            _ => Err(ToolsetError::not_configured().into()),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{invokables::Invokable, l10n::Language, state};
+use anyhow::{anyhow, Context};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use stracciatella::{unicode::Nfc, vfs::VfsLayer};
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(tag = "type")]
+pub enum ResourceEntry {
+    Dir { path: String },
+    File { path: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct List {
+    path: String,
+    mod_only: Option<bool>,
+}
+
+impl Invokable for List {
+    type Output = HashSet<ResourceEntry>;
+
+    fn name() -> &'static str {
+        "resources/list"
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.path.contains("..") {
+            return Err(anyhow!("must not contain `..`"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> anyhow::Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state.try_selected_mod()?;
+        let mut result = HashSet::new();
+        let mod_only = self.mod_only.unwrap_or(true);
+
+        list_dir(selected_mod, &self.path, mod_only, &mut result)?;
+
+        // Entries of the language folder are merged in under their unlocalized names, so a file
+        // that exists in both folders is listed once. Reading it resolves to the language folder.
+        let language = state.try_config()?.language;
+        if let Some(language) = language.filter(|l| *l != Language::English) {
+            let localized_path = if self.path.is_empty() {
+                language.id().to_owned()
+            } else {
+                format!("{}/{}", language.id(), self.path)
+            };
+            list_dir(selected_mod, &localized_path, mod_only, &mut result)?;
+        }
+
+        Ok(result)
+    }
+}
+
+/// Lists the entries of a directory of the selected mod, and of the vfs unless `mod_only` is set
+fn list_dir(
+    selected_mod: &state::OpenedMod,
+    path: &str,
+    mod_only: bool,
+    result: &mut HashSet<ResourceEntry>,
+) -> anyhow::Result<()> {
+    if !mod_only {
+        let candidates = selected_mod.vfs.read_dir(&Nfc::caseless(path)).ok();
+        for candidate in candidates.iter().flatten() {
+            let path = if path.is_empty() {
+                candidate.clone()
+            } else {
+                Nfc::caseless(&format!("{}/{}", path, candidate))
+            };
+            // Workaround to determine whether the candidate is a file
+            let is_file = selected_mod.vfs.open(&path).is_ok();
+            let entry = if is_file {
+                ResourceEntry::File {
+                    path: candidate.to_string().to_lowercase(),
+                }
+            } else {
+                ResourceEntry::Dir {
+                    path: candidate.to_string().to_lowercase(),
+                }
+            };
+
+            result.insert(entry);
+        }
+    }
+
+    let dir = selected_mod.data_path(path);
+    for entry in fs::read_dir(&dir).into_iter().flatten() {
+        let entry = entry.context("failed to read dir entry")?;
+        let candidate = Nfc::from(entry.file_name().to_string_lossy().into_owned());
+
+        let entry = if entry.path().is_file() {
+            ResourceEntry::File {
+                path: candidate.to_string().to_lowercase(),
+            }
+        } else {
+            ResourceEntry::Dir {
+                path: candidate.to_string().to_lowercase(),
+            }
+        };
+
+        result.insert(entry);
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::{invokables::Invokable, state};
 use anyhow::{anyhow, Context, Result};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{Deserialize, Serialize};
 use std::io::{BufReader, Read as _};
 
@@ -27,7 +28,18 @@ impl Serialize for Base64Sound {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl JsonSchema for Base64Sound {
+    fn schema_name() -> String {
+        "Base64Sound".to_owned()
+    }
+
+    // Serialized as a data URL
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Read {
     file: String,
 }
@@ -0,0 +1,149 @@
+use crate::encoding::Encoding;
+use crate::{files, invokables::Invokable, l10n, state};
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::io::Read as _;
+use std::path::{Component, Path};
+
+/// Extensions of files that can be edited as text
+const TEXT_EXTENSIONS: &[&str] = &["txt", "lua", "ini", "json"];
+
+fn validate_file(file: &str) -> Result<()> {
+    let path = Path::new(file);
+    if file.is_empty() {
+        return Err(anyhow!("must not be empty"));
+    }
+    if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(anyhow!("must be a relative path without `.` or `..`"));
+    }
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    if !extension.is_some_and(|e| TEXT_EXTENSIONS.contains(&e.as_str())) {
+        return Err(anyhow!(
+            "must end with one of `.{}`",
+            TEXT_EXTENSIONS.join("`, `.")
+        ));
+    }
+    Ok(())
+}
+
+/// Reads a file through the selected mod and detects its encoding
+///
+/// `expected` is the encoding reported for content that is valid in several encodings.
+fn read_text(state: &state::ToolsetState, file: &str, expected: Encoding) -> Result<TextFile> {
+    let selected_mod = state
+        .try_selected_mod()
+        .context("failed to get selected mod")?;
+    let localized = l10n::localize(state, file)?;
+    let mut bytes = vec![];
+    state
+        .open_file(&localized)
+        .context("failed to open file")?
+        .read_to_end(&mut bytes)
+        .context("failed to read file")?;
+    let encoding = Encoding::detect(&bytes, expected);
+    let content = encoding.decode(&bytes).context("failed to decode file")?;
+
+    Ok(TextFile {
+        content,
+        encoding,
+        modified: selected_mod.data_path(&localized).exists(),
+        localized: Some(localized).filter(|l| l != file),
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TextFile {
+    content: String,
+    encoding: Encoding,
+    /// Whether the selected mod provides the file
+    modified: bool,
+    /// Language specific file that was read instead of the requested one
+    localized: Option<String>,
+}
+
+/// Reads a non-JSON text file, from the selected mod if it provides the file or from vanilla otherwise
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Read {
+    file: String,
+    /// Encoding of files whose content does not reveal it, e.g. pure ASCII files, defaults to UTF-8
+    expected_encoding: Option<Encoding>,
+}
+
+impl Invokable for Read {
+    type Output = TextFile;
+
+    fn name() -> &'static str {
+        "text/read"
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_file(&self.file).context("failed to validate filename")
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        read_text(
+            &state,
+            &self.file,
+            self.expected_encoding.unwrap_or(Encoding::Utf8),
+        )
+    }
+}
+
+/// Writes a text file to the selected mod
+///
+/// Without an explicit encoding, the encoding of the current file is kept so vanilla files
+/// that are not UTF-8 are written back the way the engine expects them.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Persist {
+    file: String,
+    content: String,
+    encoding: Option<Encoding>,
+    /// Encoding of files whose content does not reveal it, e.g. pure ASCII files, defaults to UTF-8
+    expected_encoding: Option<Encoding>,
+}
+
+impl Invokable for Persist {
+    type Output = TextFile;
+
+    fn name() -> &'static str {
+        "text/persist"
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_file(&self.file).context("failed to validate filename")?;
+        if self.file.to_lowercase().ends_with(".json") {
+            return Err(anyhow!("use `json/persist` for json files"));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, state: &state::AppState) -> Result<Self::Output> {
+        let state = state.read();
+        let selected_mod = state
+            .try_selected_mod()
+            .context("failed to get selected mod")?;
+        let expected = self.expected_encoding.unwrap_or(Encoding::Utf8);
+        let encoding = match self.encoding {
+            Some(encoding) => encoding,
+            None => read_text(&state, &self.file, expected)
+                .map(|current| current.encoding)
+                .unwrap_or(expected),
+        };
+        let bytes = encoding
+            .encode(&self.content)
+            .context("failed to encode content")?;
+
+        let localized = l10n::localize(&state, &self.file)?;
+        files::write_atomic(selected_mod.data_path(&localized), bytes)
+            .context("failed to write file")?;
+
+        read_text(&state, &self.file, encoding).context("failed to get value after update")
+    }
+}
@@ -1,15 +1,21 @@
-use crate::{cache, config::PartialToolsetConfig, invokables::Invokable, state};
+use crate::{
+    cache,
+    config::PartialToolsetConfig,
+    invokables::{Invokable, InvokableDescription, Invokables},
+    state,
+};
 use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use stracciatella::{mods::ModManager, vfs::Vfs};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SerializableToolsetConfig {
     partial: bool,
     config: PartialToolsetConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ToolsetReadConfig;
 
 impl Invokable for ToolsetReadConfig {
@@ -51,7 +57,7 @@ impl Invokable for ToolsetReadConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ToolsetUpdateConfig(PartialToolsetConfig);
 
 impl Invokable for ToolsetUpdateConfig {
@@ -89,3 +95,21 @@ impl Invokable for ToolsetUpdateConfig {
             .context("failed to get toolset config after update")
     }
 }
+
+/// Lists all invokables with the JSON Schemas of their parameters and results
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListInvokables;
+
+impl Invokable for ListInvokables {
+    type Output = Vec<InvokableDescription>;
+
+    fn name() -> &'static str {
+        "toolset/listInvokables"
+    }
+
+    fn invoke(&self, _state: &state::AppState) -> Result<Self::Output> {
+        Invokables::new()
+            .describe()
+            .context("failed to describe invokables")
+    }
+}
@@ -0,0 +1,143 @@
+use anyhow::{Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::{backups, dirs, files};
+
+/// Number of changes that are kept per mod, older changes are forgotten
+const MAX_JOURNAL_ENTRIES: usize = 200;
+
+/// Serializes changes to mod files and their journals
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
+/// Waits until no other change to mod files is in progress
+///
+/// Hold the guard while loading, writing the files and saving the journal, otherwise
+/// concurrent changes could be recorded out of order or get lost.
+pub fn lock() -> MutexGuard<'static, ()> {
+    // The lock protects no data, so a panic while holding it leaves nothing inconsistent
+    JOURNAL_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn get_journal_path(mod_id: &str) -> Result<PathBuf> {
+    Ok(dirs::project_dirs()?
+        .data_dir()
+        .join("journal")
+        .join(format!("{}.json", mod_id)))
+}
+
+/// Moves the journal of a mod to a new mod id
+pub fn rename_mod(from: &str, to: &str) -> Result<()> {
+    let to = get_journal_path(to).context("failed to get journal path")?;
+    move_mod(from, &to)
+}
+
+/// Moves the journal of a mod to another path, e.g. into the trash
+pub fn move_mod(mod_id: &str, to: &Path) -> Result<()> {
+    let from = get_journal_path(mod_id).context("failed to get journal path")?;
+    if !from.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).context("failed to create journal directory")?;
+    }
+    fs::rename(from, to).context("failed to move journal")
+}
+
+/// The state of a single file before and after a change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange<T> {
+    pub file: String,
+    pub previous: T,
+    pub new: T,
+}
+
+/// A persisted change of one or more mod files, undone and redone as a whole
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry<T> {
+    pub timestamp: u64,
+    pub changes: Vec<FileChange<T>>,
+}
+
+/// Per-mod history of persisted changes
+///
+/// Entries before `position` are applied and can be undone, entries from `position` on
+/// have been undone and can be redone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Journal<T> {
+    entries: Vec<Entry<T>>,
+    position: usize,
+}
+
+impl<T> Default for Journal<T> {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            position: 0,
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> Journal<T> {
+    /// Loads the journal of a mod, a missing journal is empty
+    pub fn load(mod_id: &str) -> Result<Self> {
+        let path = get_journal_path(mod_id).context("failed to get journal path")?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path).context("failed to read journal")?;
+        let mut journal: Self =
+            serde_json::from_str(&contents).context("failed to deserialize journal")?;
+        journal.position = journal.position.min(journal.entries.len());
+
+        Ok(journal)
+    }
+
+    pub fn save(&self, mod_id: &str) -> Result<()> {
+        let path = get_journal_path(mod_id).context("failed to get journal path")?;
+        let contents = serde_json::to_string(self).context("failed to serialize journal")?;
+        files::write_atomic(path, contents).context("failed to write journal")
+    }
+
+    /// Records a new change, discarding all changes that could have been redone
+    pub fn record(&mut self, changes: Vec<FileChange<T>>) -> Result<()> {
+        self.entries.truncate(self.position);
+        self.entries.push(Entry {
+            timestamp: backups::now_millis()?,
+            changes,
+        });
+        if self.entries.len() > MAX_JOURNAL_ENTRIES {
+            let excess = self.entries.len() - MAX_JOURNAL_ENTRIES;
+            self.entries.drain(..excess);
+        }
+        self.position = self.entries.len();
+
+        Ok(())
+    }
+
+    /// Steps back and returns the change that needs to be reverted
+    pub fn undo(&mut self) -> Option<&Entry<T>> {
+        if self.position == 0 {
+            return None;
+        }
+        self.position -= 1;
+        self.entries.get(self.position)
+    }
+
+    /// Steps forward and returns the change that needs to be reapplied
+    pub fn redo(&mut self) -> Option<&Entry<T>> {
+        let entry = self.entries.get(self.position)?;
+        self.position += 1;
+        Some(entry)
+    }
+
+    pub fn entries(&self) -> &[Entry<T>] {
+        &self.entries
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
@@ -0,0 +1,157 @@
+//! Helpers to navigate the JSON schemas provided by the `SchemaManager`
+
+use anyhow::{anyhow, Result};
+use jsonschema::JSONSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Follows local `$ref`s (`#/...`) until a schema without a reference is reached
+pub fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    let mut current = schema;
+    // Guard against reference cycles
+    for _ in 0..32 {
+        match current
+            .get("$ref")
+            .and_then(|r| r.as_str())
+            .and_then(|r| r.strip_prefix('#'))
+            .and_then(|pointer| root.pointer(pointer))
+        {
+            Some(target) => current = target,
+            None => break,
+        }
+    }
+    current
+}
+
+/// Returns the schema for the items of an array schema
+pub fn items<'a>(root: &'a Value, schema: &'a Value) -> Option<&'a Value> {
+    let schema = resolve(root, schema);
+    schema.get("items").map(|items| resolve(root, items))
+}
+
+/// Returns the schema for a property of an object schema
+///
+/// Properties defined in `allOf` sub schemas are considered as well.
+pub fn property<'a>(root: &'a Value, schema: &'a Value, key: &str) -> Option<&'a Value> {
+    let schema = resolve(root, schema);
+    if let Some(property) = schema.get("properties").and_then(|p| p.get(key)) {
+        return Some(resolve(root, property));
+    }
+    if let Some(property) = schema.get("additionalProperties").filter(|p| p.is_object()) {
+        return Some(resolve(root, property));
+    }
+    schema
+        .get("allOf")
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+        .find_map(|sub_schema| property(root, sub_schema, key))
+}
+
+/// Returns the schema at a dot separated field path inside of an item schema
+pub fn field<'a>(root: &'a Value, schema: &'a Value, field: &str) -> Option<&'a Value> {
+    field.split('.').try_fold(schema, |current, key| {
+        if key.parse::<usize>().is_ok() && types(root, current).contains(&"array") {
+            items(root, current)
+        } else {
+            property(root, current, key)
+        }
+    })
+}
+
+/// Returns the schema at a JSON pointer inside of a document described by `root`
+pub fn pointer<'a>(root: &'a Value, pointer: &str) -> Option<&'a Value> {
+    if pointer.is_empty() {
+        return Some(resolve(root, root));
+    }
+    pointer
+        .strip_prefix('/')?
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .try_fold(root, |current, key| {
+            if key.parse::<usize>().is_ok() && types(root, current).contains(&"array") {
+                items(root, current)
+            } else {
+                property(root, current, &key)
+            }
+        })
+        .map(|schema| resolve(root, schema))
+}
+
+/// Returns the names and schemas of the properties of an object schema
+pub fn properties<'a>(root: &'a Value, schema: &'a Value) -> Vec<(&'a str, &'a Value)> {
+    let schema = resolve(root, schema);
+    let mut result: Vec<(&str, &Value)> = schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .into_iter()
+        .flatten()
+        .map(|(k, v)| (k.as_str(), resolve(root, v)))
+        .collect();
+    for sub_schema in schema
+        .get("allOf")
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+    {
+        for (key, value) in properties(root, sub_schema) {
+            if !result.iter().any(|(k, _)| *k == key) {
+                result.push((key, value));
+            }
+        }
+    }
+    result
+}
+
+/// Returns the types a schema allows
+///
+/// Types of `oneOf` and `anyOf` alternatives are included.
+pub fn types<'a>(root: &'a Value, schema: &'a Value) -> Vec<&'a str> {
+    let schema = resolve(root, schema);
+    let mut result = vec![];
+    match schema.get("type") {
+        Some(Value::String(t)) => result.push(t.as_str()),
+        Some(Value::Array(types)) => result.extend(types.iter().filter_map(|t| t.as_str())),
+        _ => {}
+    }
+    for key in ["oneOf", "anyOf"] {
+        for alternative in schema
+            .get(key)
+            .and_then(|a| a.as_array())
+            .into_iter()
+            .flatten()
+        {
+            for t in types(root, alternative) {
+                if !result.contains(&t) {
+                    result.push(t);
+                }
+            }
+        }
+    }
+    if result.is_empty() && schema.get("properties").is_some() {
+        result.push("object");
+    }
+    result
+}
+
+/// A location in a document that does not conform to its schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub pointer: String,
+    pub message: String,
+}
+
+/// Validates a document against a schema, returning all issues found
+pub fn validate(schema: &Value, instance: &Value) -> Result<Vec<ValidationIssue>> {
+    let compiled = JSONSchema::compile(schema).map_err(|e| anyhow!("invalid schema: {}", e))?;
+    let issues = match compiled.validate(instance) {
+        Ok(()) => vec![],
+        Err(errors) => errors
+            .map(|e| ValidationIssue {
+                pointer: e.instance_path.to_string(),
+                message: e.to_string(),
+            })
+            .collect(),
+    };
+    Ok(issues)
+}
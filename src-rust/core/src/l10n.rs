@@ -0,0 +1,135 @@
+//! Resolution of language specific variants of game resources
+//!
+//! A resource `dir/file.ext` can be overridden for a language either by a file in a
+//! language folder (`german/dir/file.ext`) or by a file with a language suffix
+//! (`dir/file.german.ext`). The folder variant takes precedence.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use stracciatella::{unicode::Nfc, vfs::VfsLayer};
+
+use crate::state::{OpenedMod, ToolsetState};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Language {
+    English,
+    Dutch,
+    French,
+    German,
+    Italian,
+    Polish,
+    Russian,
+    Chinese,
+}
+
+impl Language {
+    pub const ALL: [Language; 8] = [
+        Language::English,
+        Language::Dutch,
+        Language::French,
+        Language::German,
+        Language::Italian,
+        Language::Polish,
+        Language::Russian,
+        Language::Chinese,
+    ];
+
+    /// Identifier used in folder and file names
+    pub fn id(self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::Dutch => "dutch",
+            Language::French => "french",
+            Language::German => "german",
+            Language::Italian => "italian",
+            Language::Polish => "polish",
+            Language::Russian => "russian",
+            Language::Chinese => "chinese",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|l| l.id().eq_ignore_ascii_case(id))
+    }
+
+    /// Returns the paths that override a resource for this language, in order of precedence
+    pub fn candidates(self, file: &str) -> Vec<String> {
+        let folder = format!("{}/{}", self.id(), file);
+        let (dir, name) = match file.rfind('/') {
+            Some(index) => (&file[..=index], &file[index + 1..]),
+            None => ("", file),
+        };
+        let suffixed = match name.rfind('.') {
+            Some(index) if index > 0 => {
+                format!("{}{}.{}{}", dir, &name[..index], self.id(), &name[index..])
+            }
+            _ => format!("{}{}.{}", dir, name, self.id()),
+        };
+        vec![folder, suffixed]
+    }
+}
+
+/// Splits a localized resource path into its language and the resource it overrides
+pub fn parse_localized(file: &str) -> Option<(Language, String)> {
+    if let Some((first, rest)) = file.split_once('/') {
+        if let Some(language) = Language::from_id(first) {
+            return Some((language, rest.to_owned()));
+        }
+    }
+    let (dir, name) = match file.rfind('/') {
+        Some(index) => (&file[..=index], &file[index + 1..]),
+        None => ("", file),
+    };
+    let mut parts: Vec<_> = name.split('.').collect();
+    let (position, language) =
+        (1..parts.len()).find_map(|i| Language::from_id(parts[i]).map(|l| (i, l)))?;
+    parts.remove(position);
+
+    Some((language, format!("{}{}", dir, parts.join("."))))
+}
+
+/// Returns the resource a path overrides, or the path itself if it is not language specific
+pub fn base_resource(file: &str) -> String {
+    parse_localized(file)
+        .map(|(_, resource)| resource)
+        .unwrap_or_else(|| file.to_owned())
+}
+
+/// Returns the path a resource should be read from for a language
+///
+/// Overrides in the mod take precedence over overrides in the engine's resources. Returns the
+/// resource itself if no override exists.
+pub fn resolve(selected_mod: &OpenedMod, language: Option<Language>, file: &str) -> String {
+    let language = match language {
+        Some(language) if language != Language::English => language,
+        _ => return file.to_owned(),
+    };
+    let candidates = language.candidates(file);
+
+    candidates
+        .iter()
+        .find(|c| selected_mod.data_path(c).exists())
+        .or_else(|| {
+            candidates
+                .iter()
+                .find(|c| selected_mod.vfs.open(&Nfc::caseless(c)).is_ok())
+        })
+        .cloned()
+        .unwrap_or_else(|| file.to_owned())
+}
+
+/// Resolves the language specific variant of a resource for the configured language
+pub fn localize(state: &ToolsetState, file: &str) -> Result<String> {
+    let selected_mod = state
+        .try_selected_mod()
+        .context("failed to get selected mod")?;
+    let language = state.try_config().context("failed to get config")?.language;
+
+    Ok(resolve(selected_mod, language, file))
+}
@@ -0,0 +1,23 @@
+//! Core of the stracciatella toolset, independent of the Electron frontend
+//!
+//! Operations are implemented as invokables, which are run by name with JSON parameters
+//! through `invokables::Invokables` on an `state::AppState`.
+
+mod backups;
+pub mod cache;
+pub mod cli;
+pub mod config;
+mod csv;
+mod dirs;
+mod edt;
+mod encoding;
+pub mod error;
+mod files;
+pub mod invokables;
+mod journal;
+mod json_schema;
+mod l10n;
+mod settings;
+pub mod state;
+
+pub use l10n::Language;
@@ -0,0 +1,143 @@
+//! Toolset specific settings of a mod, stored in `toolset.json` in the mod directory
+
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::read_to_string;
+use std::path::Path;
+use stracciatella::{
+    fs::resolve_existing_components,
+    mods::{ModManager, ModPath},
+};
+
+use crate::files;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, JsonSchema)]
+pub struct ModSettings {
+    /// Mods that are layered beneath the mod when it is opened, in order of precedence
+    pub required_mods: Vec<String>,
+}
+
+impl ModSettings {
+    pub fn filename() -> &'static str {
+        "toolset.json"
+    }
+
+    /// Reads the settings of a mod, returns `None` if the mod has no settings file
+    pub fn read(mod_dir: &Path) -> Result<Option<Self>> {
+        let path = resolve_existing_components(Path::new(Self::filename()), Some(mod_dir), true);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = read_to_string(&path).context("failed to read mod settings")?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .context("failed to parse mod settings")
+    }
+
+    pub fn write(&self, mod_dir: &Path) -> Result<()> {
+        let path = resolve_existing_components(Path::new(Self::filename()), Some(mod_dir), true);
+        let json =
+            serde_json::to_string_pretty(self).context("failed to serialize mod settings")?;
+        files::write_atomic(path, json).context("failed to write mod settings")
+    }
+
+    /// Returns the settings of an installed mod, defaults if it has none
+    fn of_mod(mod_manager: &ModManager, id: &str) -> Result<Self> {
+        let m = mod_manager
+            .get_mod_by_id(id)
+            .ok_or_else(|| anyhow!("required mod `{}` is not installed", id))?;
+        let ModPath::Path(dir) = m.path();
+        Ok(Self::read(dir)
+            .with_context(|| format!("failed to read settings of mod `{}`", id))?
+            .unwrap_or_default())
+    }
+
+    /// Resolves the mods that need to be layered beneath mod `id`, highest precedence first
+    ///
+    /// Requirements of required mods are resolved recursively and placed directly beneath
+    /// the mod that requires them. Fails if a required mod is not installed or requirements
+    /// are circular.
+    pub fn resolve_required_mods(&self, mod_manager: &ModManager, id: &str) -> Result<Vec<String>> {
+        let (result, errors) = self.resolve_available_mods(mod_manager, id);
+        match errors.into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(result),
+        }
+    }
+
+    /// Resolves the required mods of mod `id` that can be layered beneath it
+    ///
+    /// Unlike `resolve_required_mods` this skips mods that are not installed and circular
+    /// requirements, and returns the problems alongside the mods.
+    pub fn resolve_available_mods(
+        &self,
+        mod_manager: &ModManager,
+        id: &str,
+    ) -> (Vec<String>, Vec<anyhow::Error>) {
+        let mut result = vec![];
+        let mut errors = vec![];
+        let mut chain = vec![id.to_owned()];
+        self.resolve_into(mod_manager, &mut chain, &mut result, &mut errors);
+        (result, errors)
+    }
+
+    fn resolve_into(
+        &self,
+        mod_manager: &ModManager,
+        chain: &mut Vec<String>,
+        result: &mut Vec<String>,
+        errors: &mut Vec<anyhow::Error>,
+    ) {
+        for required in &self.required_mods {
+            if chain.contains(required) {
+                errors.push(anyhow!(
+                    "circular mod requirement: {} -> {}",
+                    chain.join(" -> "),
+                    required
+                ));
+                continue;
+            }
+            if result.contains(required) {
+                continue;
+            }
+            if mod_manager.get_mod_by_id(required).is_none() {
+                errors.push(anyhow!(
+                    "required mod `{}` of `{}` is not installed",
+                    required,
+                    chain.join(" -> ")
+                ));
+                continue;
+            }
+            result.push(required.clone());
+            match Self::of_mod(mod_manager, required) {
+                Ok(settings) => {
+                    chain.push(required.clone());
+                    settings.resolve_into(mod_manager, chain, result, errors);
+                    chain.pop();
+                }
+                Err(e) => errors.push(e.context(format!(
+                    "failed to resolve requirements of `{}`",
+                    chain.join(" -> ")
+                ))),
+            }
+        }
+    }
+}
+
+/// Resolves the mods that need to be layered beneath an installed mod
+pub fn required_mods(mod_manager: &ModManager, id: &str) -> Result<Vec<String>> {
+    ModSettings::of_mod(mod_manager, id)?.resolve_required_mods(mod_manager, id)
+}
+
+/// Resolves the mods that can be layered beneath an installed mod, see
+/// `ModSettings::resolve_available_mods`
+pub fn available_required_mods(
+    mod_manager: &ModManager,
+    id: &str,
+) -> (Vec<String>, Vec<anyhow::Error>) {
+    match ModSettings::of_mod(mod_manager, id) {
+        Ok(settings) => settings.resolve_available_mods(mod_manager, id),
+        Err(e) => (vec![], vec![e]),
+    }
+}
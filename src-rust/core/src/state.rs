@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Context, Result};
 use std::fs::OpenOptions;
+use std::io::ErrorKind;
 use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::{
     path::{Path, PathBuf},
@@ -16,11 +17,15 @@ use stracciatella::{
 };
 
 use crate::config::{self, PartialToolsetConfig, ToolsetConfig};
+use crate::error::{ErrorCode, ToolsetError};
+use crate::settings;
 
 #[derive(Debug, Clone)]
 pub struct OpenedMod {
     pub vfs: Arc<Vfs>,
     pub m: Mod,
+    /// Mods layered beneath the mod in the VFS, highest precedence first
+    pub required_mods: Vec<Mod>,
 }
 
 impl OpenedMod {
@@ -34,21 +39,46 @@ impl OpenedMod {
             .ok_or_else(|| anyhow!("failed to find mod `{}`", mod_id))?
             .clone();
         let mut vfs = Vfs::new();
-        let engine_options = config.to_engine_options();
+        let mut engine_options = config.to_engine_options();
+        // The engine gives precedence to mods listed first
+        let (required_mods, errors) = settings::available_required_mods(mod_manager, mod_id);
+        // A broken requirement should not keep the mod from being opened, so it can be fixed
+        for e in &errors {
+            log::warn!("opening `{}` without some required mods: {:#}", mod_id, e);
+        }
+        engine_options.mods = required_mods;
         vfs.init(&engine_options, mod_manager)
             .map_err(|e| anyhow!("failed to initialize vfs: {}", e))?;
+        let required_mods = engine_options
+            .mods
+            .iter()
+            .filter_map(|id| mod_manager.get_mod_by_id(id).cloned())
+            .collect();
 
         Ok(OpenedMod {
             vfs: Arc::new(vfs),
             m,
+            required_mods,
         })
     }
 
     pub fn data_path(&self, file_path: &str) -> PathBuf {
-        let file_path = PathBuf::from("data").join(file_path);
-        match self.m.path() {
-            ModPath::Path(p) => resolve_existing_components(Path::new(&file_path), Some(p), true),
-        }
+        mod_data_path(&self.m, file_path)
+    }
+
+    /// Returns the data directories of the required mods, highest precedence first
+    pub fn required_data_dirs(&self) -> Vec<PathBuf> {
+        self.required_mods
+            .iter()
+            .map(|m| mod_data_path(m, ""))
+            .collect()
+    }
+}
+
+fn mod_data_path(m: &Mod, file_path: &str) -> PathBuf {
+    let file_path = PathBuf::from("data").join(file_path);
+    match m.path() {
+        ModPath::Path(p) => resolve_existing_components(Path::new(&file_path), Some(p), true),
     }
 }
 
@@ -88,28 +118,33 @@ impl ToolsetState {
                 opened_mod: Some(opened_mod),
                 ..
             } => Ok(opened_mod),
-            _ => Err(anyhow!("no mod selected")),
+            _ => Err(ToolsetError::no_mod_selected().into()),
         }
     }
 
     pub fn try_schema_manager(&self) -> Result<&SchemaManager> {
         match self {
             ToolsetState::Configured { schema_manager, .. } => Ok(schema_manager),
-            _ => Err(anyhow!("schema manager not initialized")),
+            _ => Err(
+                ToolsetError::new(ErrorCode::NotConfigured, "schema manager not initialized")
+                    .into(),
+            ),
         }
     }
 
     pub fn try_mod_manager(&self) -> Result<&ModManager> {
         match self {
             ToolsetState::Configured { mod_manager, .. } => Ok(mod_manager),
-            _ => Err(anyhow!("mod manager not initialized")),
+            _ => Err(
+                ToolsetError::new(ErrorCode::NotConfigured, "mod manager not initialized").into(),
+            ),
         }
     }
 
     pub fn try_config(&self) -> Result<&ToolsetConfig> {
         match self {
             ToolsetState::Configured { config, .. } => Ok(config),
-            _ => Err(anyhow!("config not initialized")),
+            _ => Err(ToolsetError::new(ErrorCode::NotConfigured, "config not initialized").into()),
         }
     }
 
@@ -122,16 +157,22 @@ impl ToolsetState {
         Ok(if path.exists() {
             Box::new(
                 OpenOptions::new()
+                    .read(true)
                     .open(&path)
                     .context("failed to open file from mod")?,
             )
         } else {
-            Box::new(
-                selected_mod
-                    .vfs
-                    .open(&Nfc::caseless(file))
-                    .context("failed to open file from vfs")?,
-            )
+            Box::new(selected_mod.vfs.open(&Nfc::caseless(file)).map_err(|e| {
+                // Other failures, e.g. missing permissions, are not a missing file
+                if e.kind() == ErrorKind::NotFound {
+                    anyhow::Error::new(e).context(
+                        ToolsetError::new(ErrorCode::FileNotFound, "failed to open file from vfs")
+                            .with_file(file),
+                    )
+                } else {
+                    anyhow::Error::new(e).context("failed to open file from vfs")
+                }
+            })?)
         })
     }
 }
@@ -156,5 +197,3 @@ impl AppState {
         self.inner.write().expect("state poisoned")
     }
 }
-
-impl neon::types::Finalize for AppState {}
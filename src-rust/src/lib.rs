@@ -1,18 +1,15 @@
 use std::{ops::Deref, sync::LazyLock};
 
-use invokables::Invokables;
 use neon::prelude::*;
 use simplelog::*;
+use stracciatella_toolset_core::{config, invokables::Invokables, state};
 
-mod cache;
-mod config;
-mod dirs;
-mod invokables;
-mod state;
+/// Wraps the app state so it can be handed to JavaScript
+struct BoxedAppState(state::AppState);
 
-use state::AppState;
+impl Finalize for BoxedAppState {}
 
-static INVOKABLES: LazyLock<Invokables> = LazyLock::new(|| Invokables::new());
+static INVOKABLES: LazyLock<Invokables> = LazyLock::new(Invokables::new);
 
 fn init_logger(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
     if let Err(e) = TermLogger::init(
@@ -26,7 +23,7 @@ fn init_logger(mut ctx: FunctionContext) -> JsResult<JsUndefined> {
     Ok(JsUndefined::new(&mut ctx))
 }
 
-fn new_app_state(mut ctx: FunctionContext) -> JsResult<JsBox<state::AppState>> {
+fn new_app_state(mut ctx: FunctionContext) -> JsResult<JsBox<BoxedAppState>> {
     if let Ok(toolset_config_path) = config::ToolsetConfig::path() {
         log::info!(
             "Toolset config path: `{}`",
@@ -42,14 +39,15 @@ fn new_app_state(mut ctx: FunctionContext) -> JsResult<JsBox<state::AppState>> {
     };
     let initial_state = state::AppState::new(initial_state);
 
-    Ok(ctx.boxed(initial_state))
+    Ok(ctx.boxed(BoxedAppState(initial_state)))
 }
 
 fn invoke(mut ctx: FunctionContext) -> JsResult<JsPromise> {
-    let state: AppState = ctx
-        .argument::<JsBox<state::AppState>>(0)?
+    let state: state::AppState = ctx
+        .argument::<JsBox<BoxedAppState>>(0)?
         .deref()
         .deref()
+        .0
         .clone();
     let payload: String = ctx.argument::<JsString>(1)?.value(&mut ctx);
     let (deferred, promise) = ctx.promise();
@@ -65,7 +63,12 @@ fn invoke(mut ctx: FunctionContext) -> JsResult<JsPromise> {
                 deferred.resolve(&mut ctx, val)
             }
             Err(e) => {
-                let err = ctx.error(format!("{:#}", e))?;
+                let err = ctx.error(e.to_string())?;
+                // The structured error is passed as JSON, so the frontend can react to its code
+                if let Ok(details) = serde_json::to_string(&e) {
+                    let details = ctx.string(details);
+                    err.set(&mut ctx, "details", details)?;
+                }
                 deferred.reject(&mut ctx, err)
             }
         };
@@ -148,6 +148,33 @@ describe('files state', () => {
         data: null,
       });
     });
+
+    it('should keep the code of structured invoke errors', async () => {
+      const appStore = createAppStore();
+      const invokeMock = getInvokeMock();
+
+      invokeMock.fail(
+        'json/read',
+        { file: TEST_FILE },
+        {
+          code: 'noModSelected',
+          message: 'no mod selected',
+          context: ['failed to get selected mod', 'no mod selected'],
+        },
+      );
+
+      await appStore.dispatch(loadJSON(TEST_FILE));
+
+      expect(appStore.getState().files.disk[TEST_FILE]).toMatchObject({
+        loading: false,
+        loadingError: {
+          name: 'InvokeError',
+          code: 'noModSelected',
+          message: 'failed to get selected mod: no mod selected',
+        },
+        data: null,
+      });
+    });
   });
 
   describe('changeJson', () => {
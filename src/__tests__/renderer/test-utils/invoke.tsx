@@ -5,6 +5,7 @@ import {
   InvokableInput,
   InvokableOutput,
 } from 'src/common/invokables';
+import { SerializedInvokeError } from 'src/common/invokables/errors';
 import { MockedFunction } from 'vitest';
 
 type MockedInvoke = MockedFunction<typeof window.electronAPI.invoke>;
@@ -17,6 +18,10 @@ type InvokeResult =
   | {
       type: 'resolve';
       output: unknown;
+    }
+  | {
+      type: 'fail';
+      error: SerializedInvokeError;
     };
 
 export class InvokeMock {
@@ -46,7 +51,10 @@ export class InvokeMock {
         })
       ) {
         if (call.result.type === 'resolve') {
-          return call.result.output;
+          return { result: call.result.output };
+        }
+        if (call.result.type === 'fail') {
+          return { error: call.result.error };
         }
         throw call.result.error;
       }
@@ -85,6 +93,21 @@ export class InvokeMock {
       },
     });
   }
+
+  fail<T extends AnyInvokableName>(
+    name: T,
+    input: InvokableInput<InvokableFromName<T>>,
+    error: SerializedInvokeError,
+  ) {
+    this.expectedCalls.push({
+      name,
+      input,
+      result: {
+        type: 'fail',
+        error,
+      },
+    });
+  }
 }
 
 export function getInvokeMock() {
@@ -0,0 +1,50 @@
+import z from 'zod';
+import { InvokableDefinition } from '.';
+
+type Category = 'edt';
+
+const EDT_FILE_SCHEMA = z.object({
+  recordLength: z.number(),
+  records: z.array(z.string()),
+  modified: z.boolean(),
+  localized: z.nullable(z.string()),
+});
+
+export type EdtFile = z.infer<typeof EDT_FILE_SCHEMA>;
+
+const READ_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  recordLength: z.number().int().positive(),
+});
+
+export type EdtReadInvokable = InvokableDefinition<
+  Category,
+  'read',
+  z.infer<typeof READ_INPUT_SCHEMA>,
+  z.infer<typeof EDT_FILE_SCHEMA>
+>;
+
+export const edtReadInvokableDefinition: EdtReadInvokable = {
+  name: 'edt/read',
+  inputSchema: READ_INPUT_SCHEMA,
+  outputSchema: EDT_FILE_SCHEMA,
+};
+
+const PERSIST_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  recordLength: z.number().int().positive(),
+  records: z.array(z.string()),
+});
+
+export type EdtPersistInvokable = InvokableDefinition<
+  Category,
+  'persist',
+  z.infer<typeof PERSIST_INPUT_SCHEMA>,
+  z.infer<typeof EDT_FILE_SCHEMA>
+>;
+
+export const edtPersistInvokableDefinition: EdtPersistInvokable = {
+  name: 'edt/persist',
+  inputSchema: PERSIST_INPUT_SCHEMA,
+  outputSchema: EDT_FILE_SCHEMA,
+};
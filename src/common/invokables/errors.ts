@@ -0,0 +1,40 @@
+import z from 'zod';
+import { ErrorCode, InvokeError as RustInvokeError } from './generated';
+
+export type { ErrorCode };
+
+export const INVOKE_ERROR_SCHEMA = z.object({
+  // The codes are generated from Rust, the backend is trusted to send a known one
+  code: z.custom<ErrorCode>((code) => typeof code === 'string'),
+  message: z.string(),
+  context: z.array(z.string()),
+  file: z.optional(z.string()),
+  pointer: z.optional(z.string()),
+}) satisfies z.ZodType<RustInvokeError>;
+
+export type SerializedInvokeError = z.infer<typeof INVOKE_ERROR_SCHEMA>;
+
+// The error branch comes first, as the result branch would also accept an error response
+export const INVOKE_RESPONSE_SCHEMA = z.union([
+  z.object({ error: INVOKE_ERROR_SCHEMA }),
+  z.object({ result: z.unknown() }),
+]);
+
+export type InvokeResponse = z.infer<typeof INVOKE_RESPONSE_SCHEMA>;
+
+/** Failure of an invokable, `code` allows reacting to specific failures */
+export class InvokeError extends Error {
+  code: ErrorCode;
+  context: string[];
+  file?: string;
+  pointer?: string;
+
+  constructor(error: SerializedInvokeError) {
+    super(error.context.join(': '));
+    this.name = 'InvokeError';
+    this.code = error.code;
+    this.context = error.context;
+    this.file = error.file;
+    this.pointer = error.pointer;
+  }
+}
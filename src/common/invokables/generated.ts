@@ -0,0 +1,995 @@
+// Generated by .erb/scripts/generate-invokable-types.mts from the Rust invokables, do not edit
+
+/** Writes the records of an EDT file to the selected mod */
+export type EdtPersistInput = {
+  file: string;
+  recordLength: number;
+  records: string[];
+};
+
+export type EdtPersistOutput = {
+  /** Language specific file that was read instead of the requested one */
+  localized?: string | null;
+  /** Whether the selected mod provides the file */
+  modified: boolean;
+  /** Length of each record in characters */
+  recordLength: number;
+  records: string[];
+};
+
+/** Reads the records of an EDT file, e.g. `binarydata/itemdesc.edt` */
+export type EdtReadInput = {
+  file: string;
+  recordLength: number;
+};
+
+export type EdtReadOutput = {
+  /** Language specific file that was read instead of the requested one */
+  localized?: string | null;
+  /** Whether the selected mod provides the file */
+  modified: boolean;
+  /** Length of each record in characters */
+  recordLength: number;
+  records: string[];
+};
+
+export type ImageReadMetadataInput = {
+  file: string;
+};
+
+export type ImageReadMetadataOutput = {
+  images: SubImageMetadata[];
+};
+
+export type ImageRenderInput = {
+  file: string;
+  subimage?: number | null;
+};
+
+export type ImageRenderOutput = string;
+
+/** Updates fields of all items of a file matching a filter */
+export type JsonBulkUpdateInput = {
+  assignments: Assignment[];
+  dryRun?: boolean | null;
+  file: Filename;
+  filter?: Predicate | null;
+  saveMode?: SaveMode | null;
+};
+
+export type JsonBulkUpdateOutput = {
+  changes: BulkChange[];
+  file?: JsonFileWithSchema | null;
+};
+
+/** Validates all JSON values and patches of the selected mod against the current schemas */
+export type JsonCheckSchemaCompatibilityInput = null;
+
+export type JsonCheckSchemaCompatibilityOutput = {
+  files: FileCompatibility[];
+  pendingMigrations: PendingMigration[];
+};
+
+/** Describes the field at a JSON pointer of a file using the file's schema */
+export type JsonDescribeInput = {
+  file: Filename;
+  pointer: string;
+};
+
+export type JsonDescribeOutput = {
+  default?: unknown;
+  description?: string | null;
+  enum?: unknown[] | null;
+  /** Whether `maximum` itself is excluded from the allowed values */
+  exclusiveMaximum: boolean;
+  /** Whether `minimum` itself is excluded from the allowed values */
+  exclusiveMinimum: boolean;
+  maximum?: unknown;
+  minimum?: unknown;
+  title?: string | null;
+  types: string[];
+};
+
+export type JsonDiffInput = {
+  file: Filename;
+};
+
+export type JsonDiffOutput = {
+  added: ItemSummary[];
+  changed: ItemDiff[];
+  file: string;
+  keyProperty?: string | null;
+  markdown: string;
+  removed: ItemSummary[];
+};
+
+/** Exports an array-of-objects file (e.g. `weapons.json`) as CSV to a path on the host */
+export type JsonExportCsvInput = {
+  file: Filename;
+  path: string;
+};
+
+export type JsonExportCsvOutput = {
+  columns: string[];
+  rows: number;
+};
+
+/** Lists the persisted changes of the selected mod, oldest first */
+export type JsonHistoryInput = null;
+
+export type JsonHistoryOutput = {
+  canRedo: boolean;
+  canUndo: boolean;
+  entries: HistoryEntry[];
+};
+
+/**
+ * Imports a CSV file previously created by `json/exportCsv`, replacing all items of the file
+ *
+ * Nothing is written if any cell fails to convert to the type required by the schema, or if the resulting document does not validate against the schema.
+ */
+export type JsonImportCsvInput = {
+  file: Filename;
+  path: string;
+  saveMode?: SaveMode | null;
+};
+
+export type JsonImportCsvOutput = {
+  errors: CsvCellError[];
+  file?: JsonFileWithSchema | null;
+};
+
+export type JsonListBackupsInput = {
+  file: Filename;
+};
+
+export type JsonListBackupsOutput = Backup[];
+
+export type JsonListSchemasInput = null;
+
+export type JsonListSchemasOutput = SchemaEntry[];
+
+/** Applies all pending migrations to the selected mod */
+export type JsonMigrateInput = null;
+
+export type JsonMigrateOutput = {
+  applied: PendingMigration[];
+};
+
+export type JsonPersistInput = {
+  file: Filename;
+  patch?: unknown[] | null;
+  value?: unknown;
+};
+
+export type JsonPersistOutput = {
+  /** Language specific file that was read instead of the requested one */
+  localized?: string | null;
+  patch?: unknown[] | null;
+  schema: unknown;
+  value?: unknown;
+  vanilla: unknown;
+};
+
+export type JsonReadInput = {
+  file: Filename;
+};
+
+export type JsonReadOutput = {
+  /** Language specific file that was read instead of the requested one */
+  localized?: string | null;
+  patch?: unknown[] | null;
+  schema: unknown;
+  value?: unknown;
+  vanilla: unknown;
+};
+
+/** Reapplies the most recently undone change of the selected mod */
+export type JsonRedoInput = null;
+
+export type JsonRedoOutput = {
+  files: JournalStepFile[];
+  timestamp: number;
+};
+
+/** Renames an identifier (e.g. an item's `internalName`) and updates all references to it */
+export type JsonRenameIdentifierInput = {
+  file: Filename;
+  from: string;
+  preview?: boolean | null;
+  to: string;
+};
+
+export type JsonRenameIdentifierOutput = {
+  applied: boolean;
+  changes: IdentifierChange[];
+};
+
+/** Renders every schema the engine knows into a browsable reference in a host directory */
+export type JsonRenderSchemaReferenceInput = {
+  dir: string;
+  format: ReferenceFormat;
+};
+
+export type JsonRenderSchemaReferenceOutput = {
+  index: string;
+  pages: number;
+};
+
+export type JsonRestoreBackupInput = {
+  file: Filename;
+  timestamp: number;
+};
+
+export type JsonRestoreBackupOutput = {
+  /** Language specific file that was read instead of the requested one */
+  localized?: string | null;
+  patch?: unknown[] | null;
+  schema: unknown;
+  value?: unknown;
+  vanilla: unknown;
+};
+
+export type JsonSearchInput = {
+  query: SearchQuery;
+};
+
+export type JsonSearchOutput = SearchHit[];
+
+/** Reverts the most recent persisted change of the selected mod */
+export type JsonUndoInput = null;
+
+export type JsonUndoOutput = {
+  files: JournalStepFile[];
+  timestamp: number;
+};
+
+/** Lists the localized resources of the selected mod for each language */
+export type L10nCoverageInput = null;
+
+export type L10nCoverageOutput = LanguageCoverage[];
+
+/** Copies a mod to a new id, e.g. to try out a variant of it */
+export type ModCloneInput = {
+  id: string;
+  /** Name of the copy, defaults to the name of the original */
+  name?: string | null;
+  newId: string;
+};
+
+export type ModCloneOutput = {
+  description?: string;
+  id: string;
+  name: string;
+  path: string;
+  version: string;
+};
+
+export type ModCreateInput = {
+  description?: string | null;
+  id: string;
+  name: string;
+  version: string;
+};
+
+export type ModCreateOutput = {
+  description?: string;
+  id: string;
+  name: string;
+  path: string;
+  version: string;
+};
+
+/**
+ * Deletes a mod by moving it to the toolset's trash folder
+ *
+ * Backups and the change history of the mod are moved to the trash next to it. If the mod is currently opened, it is closed, any other opened mod is reopened without it.
+ */
+export type ModDeleteInput = {
+  id: string;
+};
+
+export type ModDeleteOutput = {
+  /** Directory the mod was moved to */
+  trashPath: string;
+};
+
+/**
+ * Finds files of the selected mod that are identical to the files beneath the mod
+ *
+ * Binary files are compared byte by byte, JSON files by value. Patches are redundant if applying them changes nothing. With `delete`, the redundant files are moved to the toolset's trash folder.
+ */
+export type ModFindRedundantInput = {
+  delete: boolean;
+};
+
+export type ModFindRedundantOutput = {
+  /** Files in the data directory of the mod that change nothing */
+  files: string[];
+  /** Directory the files were moved to, if they were deleted */
+  trashPath?: string | null;
+};
+
+/**
+ * Installs a mod from a zip file into the `mods` folder of the stracciatella home
+ *
+ * The archive needs to contain a single folder named like the mod id, containing the mod's `manifest.json`. This is the layout that `mod/package` produces.
+ */
+export type ModInstallInput = {
+  path: string;
+};
+
+export type ModInstallOutput = {
+  description?: string;
+  id: string;
+  name: string;
+  path: string;
+  version: string;
+};
+
+/**
+ * Runs health checks on the selected mod, e.g. before releasing it
+ *
+ * Findings are sorted by severity, errors first.
+ */
+export type ModLintInput = null;
+
+export type ModLintOutput = LintFinding[];
+
+/**
+ * Packages the selected mod into a zip file that can be extracted into the `mods` folder
+ *
+ * The archive contains a single folder named like the mod id with the manifest, the data folder and any extra files or folders of the mod directory that were requested. A `.sha256` checksum file is written next to the archive.
+ */
+export type ModPackageInput = {
+  extras?: string[] | null;
+  path: string;
+};
+
+export type ModPackageOutput = {
+  checksumPath: string;
+  files: number;
+  path: string;
+  sha256: string;
+};
+
+/** Reads the whole `manifest.json` of the selected mod, including fields the toolset does not edit by name */
+export type ModReadManifestInput = null;
+
+/** A mod's `manifest.json`, fields the toolset does not know about are preserved */
+export type ModReadManifestOutput = {
+  description?: string | null;
+  name: string;
+  version: string;
+} & Record<string, unknown>;
+
+export type ModReadSelectedInput = null;
+
+export type ModReadSelectedOutput = Mod | null;
+
+/**
+ * Changes the id and with it the directory of a mod
+ *
+ * Backups and the change history of the mod are moved along. Other mods requiring the mod and the last selected mod in the config are updated to the new id.
+ */
+export type ModRenameInput = {
+  id: string;
+  newId: string;
+};
+
+export type ModRenameOutput = {
+  description?: string;
+  id: string;
+  name: string;
+  path: string;
+  version: string;
+};
+
+/**
+ * Updates the `manifest.json` of the selected mod
+ *
+ * Fields of the manifest that are not part of the request are kept as they are.
+ */
+export type ModUpdateManifestInput = {
+  description?: string | null;
+  /** Further fields to set, e.g. ones a newer engine understands, `null` removes a field */
+  fields?: Record<string, unknown> | null;
+  name: string;
+  version: string;
+};
+
+export type ModUpdateManifestOutput = {
+  description?: string;
+  id: string;
+  name: string;
+  path: string;
+  version: string;
+};
+
+export type ModUpdateSelectedInput = EditableMod;
+
+export type ModUpdateSelectedOutput = {
+  description?: string;
+  id: string;
+  name: string;
+  path: string;
+  version: string;
+};
+
+/**
+ * Updates the `toolset.json` of a mod and reopens the opened mod with the new settings
+ *
+ * The mod does not need to be opened, so settings that keep a mod from opening can be fixed. Fails without writing anything if a required mod is missing or requirements are circular.
+ */
+export type ModUpdateSettingsInput = {
+  /** Mod to update, defaults to the selected mod */
+  id?: string | null;
+  /** Mods that are layered beneath the mod when it is opened, in order of precedence */
+  required_mods: string[];
+};
+
+export type ModUpdateSettingsOutput = {
+  /** Mods that are layered beneath the mod when it is opened, in order of precedence */
+  required_mods: string[];
+};
+
+/**
+ * Reports resources that are provided by more than one of the given mods
+ *
+ * A JSON patch counts as providing the file it patches.
+ */
+export type ModsConflictsInput = {
+  ids: string[];
+};
+
+export type ModsConflictsOutput = ResourceConflict[];
+
+export type ModsListAvailableInput = null;
+
+export type ModsListAvailableOutput = Mod[];
+
+export type ModsListEditableInput = null;
+
+export type ModsListEditableOutput = EditableMod[];
+
+export type ResourcesListInput = {
+  modOnly?: boolean | null;
+  path: string;
+};
+
+export type ResourcesListOutput = ResourceEntry[];
+
+export type SoundReadInput = {
+  file: string;
+};
+
+export type SoundReadOutput = string;
+
+/**
+ * Writes a text file to the selected mod
+ *
+ * Without an explicit encoding, the encoding of the current file is kept so vanilla files that are not UTF-8 are written back the way the engine expects them.
+ */
+export type TextPersistInput = {
+  content: string;
+  encoding?: Encoding | null;
+  /** Encoding of files whose content does not reveal it, e.g. pure ASCII files, defaults to UTF-8 */
+  expectedEncoding?: Encoding | null;
+  file: string;
+};
+
+export type TextPersistOutput = {
+  content: string;
+  encoding: Encoding;
+  /** Language specific file that was read instead of the requested one */
+  localized?: string | null;
+  /** Whether the selected mod provides the file */
+  modified: boolean;
+};
+
+/** Reads a non-JSON text file, from the selected mod if it provides the file or from vanilla otherwise */
+export type TextReadInput = {
+  /** Encoding of files whose content does not reveal it, e.g. pure ASCII files, defaults to UTF-8 */
+  expectedEncoding?: Encoding | null;
+  file: string;
+};
+
+export type TextReadOutput = {
+  content: string;
+  encoding: Encoding;
+  /** Language specific file that was read instead of the requested one */
+  localized?: string | null;
+  /** Whether the selected mod provides the file */
+  modified: boolean;
+};
+
+/** Lists all invokables with the JSON Schemas of their parameters and results */
+export type ToolsetListInvokablesInput = null;
+
+export type ToolsetListInvokablesOutput = InvokableDescription[];
+
+export type ToolsetReadConfigInput = null;
+
+export type ToolsetReadConfigOutput = {
+  config: PartialToolsetConfig;
+  partial: boolean;
+};
+
+export type ToolsetUpdateConfigInput = PartialToolsetConfig;
+
+export type ToolsetUpdateConfigOutput = {
+  config: PartialToolsetConfig;
+  partial: boolean;
+};
+
+/** Serializable form of a failed invocation */
+export type InvokeError = {
+  code: ErrorCode;
+  /** Messages of the whole chain, from the outermost context to the root cause */
+  context: string[];
+  /** File the failure relates to */
+  file?: string | null;
+  /** Message of the root cause */
+  message: string;
+  /** JSON pointer into the file the failure relates to */
+  pointer?: string | null;
+};
+
+/** An update of a single field, `field` is a dot separated path into the item */
+export type Assignment = {
+  field: string;
+  op: 'set';
+  value: unknown;
+} | {
+  field: string;
+  op: 'add';
+  value: number;
+} | {
+  field: string;
+  op: 'multiply';
+  value: number;
+};
+
+export type Backup = {
+  timestamp: number;
+};
+
+export type BulkChange = {
+  field: string;
+  itemName?: string | null;
+  newValue: unknown;
+  oldValue?: unknown;
+  pointer: string;
+};
+
+export type CompatibilityIssue = {
+  message: string;
+  pointer: string;
+  source: IssueSource;
+};
+
+export type ConflictingField = {
+  /** Dotted path of the field in the item, `*` for the item as a whole */
+  field: string;
+  /** Key of the item, see `json/diff` */
+  key: string;
+  mods: string[];
+};
+
+export type CsvCellError = {
+  column: string;
+  message: string;
+  /** Row in the CSV file, the header being row 1 */
+  row: number;
+  value: string;
+};
+
+export type EditableMod = {
+  description?: string;
+  id: string;
+  name: string;
+  path: string;
+  settings?: ModSettings | null;
+  /** Why the settings could not be read or resolved, e.g. a required mod is missing */
+  settingsError?: string | null;
+  version: string;
+};
+
+export type Encoding = 'utf8' | 'utf16Le' | 'utf16Be' | 'utf8Bom' | 'windows1252';
+
+/** Machine readable kind of a failure */
+export type ErrorCode = 'unknownInvokable' | 'invalidParams' | 'notConfigured' | 'noModSelected' | 'fileNotFound' | 'schemaNotFound' | 'pointerNotFound' | 'internal';
+
+export type FieldDiff = {
+  field: string;
+  newValue?: unknown;
+  oldValue?: unknown;
+};
+
+export type FileCompatibility = {
+  file: string;
+  issues: CompatibilityIssue[];
+};
+
+export type Filename = string;
+
+export type HistoryEntry = {
+  files: string[];
+  timestamp: number;
+  undone: boolean;
+};
+
+export type IdentifierChange = {
+  file: string;
+  itemName?: string | null;
+  newValue: unknown;
+  oldValue: unknown;
+  pointer: string;
+};
+
+/** Name and JSON Schemas of the parameters and result of an invokable */
+export type InvokableDescription = {
+  inputSchema: unknown;
+  name: string;
+  outputSchema: unknown;
+};
+
+/** Part of a mod file an issue was found in */
+export type IssueSource = 'file' | 'value' | 'patch';
+
+export type ItemDiff = {
+  fields: FieldDiff[];
+  key: string;
+  name?: string | null;
+};
+
+export type ItemSummary = {
+  key: string;
+  name?: string | null;
+  value: unknown;
+};
+
+export type JournalStepFile = {
+  file: string;
+  json: JsonFileWithSchema;
+};
+
+export type JsonConflict = {
+  /** Fields that more than one mod changes to different values */
+  fields: ConflictingField[];
+  kind: JsonConflictKind;
+};
+
+/** How the overrides of a JSON file by multiple mods relate to each other */
+export type JsonConflictKind = 'identical' | 'differentItems' | 'differentFields' | 'sameFields';
+
+export type JsonFileWithSchema = {
+  /** Language specific file that was read instead of the requested one */
+  localized?: string | null;
+  patch?: unknown[] | null;
+  schema: unknown;
+  value?: unknown;
+  vanilla: unknown;
+};
+
+export type Language = 'english' | 'dutch' | 'french' | 'german' | 'italian' | 'polish' | 'russian' | 'chinese';
+
+export type LanguageCoverage = {
+  language: Language;
+  /** Resources that are localized for other languages, but not for this one */
+  missing: string[];
+  resources: LocalizedResource[];
+};
+
+export type LintCheck = 'manifest' | 'unknownFile' | 'caseCollision' | 'schema' | 'patch' | 'emptyDirectory' | 'identicalToVanilla';
+
+export type LintFinding = {
+  check: LintCheck;
+  /** Path relative to the mod directory */
+  file?: string | null;
+  message: string;
+  /** JSON pointer into the file */
+  pointer?: string | null;
+  severity: Severity;
+};
+
+export type LocalizedResource = {
+  /** Path of the language specific file */
+  file: string;
+  /** Resource that is overridden by the file */
+  resource: string;
+};
+
+export type Mod = {
+  description?: string;
+  id: string;
+  name: string;
+  path: string;
+  version: string;
+};
+
+export type ModSettings = {
+  /** Mods that are layered beneath the mod when it is opened, in order of precedence */
+  required_mods: string[];
+};
+
+export type PartialToolsetConfig = {
+  language?: Language | null;
+  lastSelectedMod?: string | null;
+  stracciatellaHome?: string | null;
+  stracciatellaInstallDir?: string | null;
+  vanillaGameDir?: string | null;
+};
+
+export type PendingMigration = {
+  description: string;
+  files: string[];
+  id: string;
+};
+
+/**
+ * A condition on a single field of a JSON item
+ *
+ * `field` is a dot separated path into the item, e.g. `inventoryGraphics.small.path`.
+ */
+export type Predicate = {
+  field: string;
+  op: 'equals';
+  value: unknown;
+} | {
+  field: string;
+  op: 'contains';
+  value: unknown;
+} | {
+  field: string;
+  max?: number | null;
+  min?: number | null;
+  op: 'range';
+};
+
+export type ReferenceFormat = 'markdown' | 'html';
+
+export type ResourceConflict = {
+  /** Set if the JSON overrides could not be analyzed */
+  error?: string | null;
+  /** Analysis of the overrides for JSON resources */
+  json?: JsonConflict | null;
+  /** Mods that provide the resource, in the order they were requested */
+  mods: string[];
+  resource: string;
+};
+
+export type ResourceEntry = {
+  path: string;
+  type: 'Dir';
+} | {
+  path: string;
+  type: 'File';
+};
+
+/** How a changed document is stored in a mod */
+export type SaveMode = 'patch' | 'replace';
+
+export type SchemaEntry = {
+  description?: string | null;
+  file: string;
+  hasPatch: boolean;
+  hasValue: boolean;
+  title?: string | null;
+};
+
+export type SearchHit = {
+  file: string;
+  itemName?: string | null;
+  pointer: string;
+  value: unknown;
+};
+
+export type SearchQuery = {
+  caseSensitive?: boolean | null;
+  text: string;
+  type: 'text';
+} | {
+  pattern: string;
+  type: 'pointer';
+} | {
+  predicate: Predicate;
+  type: 'predicate';
+};
+
+export type Severity = 'error' | 'warning' | 'info';
+
+export type SubImageMetadata = {
+  height: number;
+  offset_x: number;
+  offset_y: number;
+  width: number;
+};
+
+/** Parameters and results of the invokables implemented in Rust by name */
+export type RustInvokables = {
+  'edt/persist': {
+    input: EdtPersistInput;
+    output: EdtPersistOutput;
+  };
+  'edt/read': {
+    input: EdtReadInput;
+    output: EdtReadOutput;
+  };
+  'image/readMetadata': {
+    input: ImageReadMetadataInput;
+    output: ImageReadMetadataOutput;
+  };
+  'image/render': {
+    input: ImageRenderInput;
+    output: ImageRenderOutput;
+  };
+  'json/bulkUpdate': {
+    input: JsonBulkUpdateInput;
+    output: JsonBulkUpdateOutput;
+  };
+  'json/checkSchemaCompatibility': {
+    input: JsonCheckSchemaCompatibilityInput;
+    output: JsonCheckSchemaCompatibilityOutput;
+  };
+  'json/describe': {
+    input: JsonDescribeInput;
+    output: JsonDescribeOutput;
+  };
+  'json/diff': {
+    input: JsonDiffInput;
+    output: JsonDiffOutput;
+  };
+  'json/exportCsv': {
+    input: JsonExportCsvInput;
+    output: JsonExportCsvOutput;
+  };
+  'json/history': {
+    input: JsonHistoryInput;
+    output: JsonHistoryOutput;
+  };
+  'json/importCsv': {
+    input: JsonImportCsvInput;
+    output: JsonImportCsvOutput;
+  };
+  'json/listBackups': {
+    input: JsonListBackupsInput;
+    output: JsonListBackupsOutput;
+  };
+  'json/listSchemas': {
+    input: JsonListSchemasInput;
+    output: JsonListSchemasOutput;
+  };
+  'json/migrate': {
+    input: JsonMigrateInput;
+    output: JsonMigrateOutput;
+  };
+  'json/persist': {
+    input: JsonPersistInput;
+    output: JsonPersistOutput;
+  };
+  'json/read': {
+    input: JsonReadInput;
+    output: JsonReadOutput;
+  };
+  'json/redo': {
+    input: JsonRedoInput;
+    output: JsonRedoOutput;
+  };
+  'json/renameIdentifier': {
+    input: JsonRenameIdentifierInput;
+    output: JsonRenameIdentifierOutput;
+  };
+  'json/renderSchemaReference': {
+    input: JsonRenderSchemaReferenceInput;
+    output: JsonRenderSchemaReferenceOutput;
+  };
+  'json/restoreBackup': {
+    input: JsonRestoreBackupInput;
+    output: JsonRestoreBackupOutput;
+  };
+  'json/search': {
+    input: JsonSearchInput;
+    output: JsonSearchOutput;
+  };
+  'json/undo': {
+    input: JsonUndoInput;
+    output: JsonUndoOutput;
+  };
+  'l10n/coverage': {
+    input: L10nCoverageInput;
+    output: L10nCoverageOutput;
+  };
+  'mod/clone': {
+    input: ModCloneInput;
+    output: ModCloneOutput;
+  };
+  'mod/create': {
+    input: ModCreateInput;
+    output: ModCreateOutput;
+  };
+  'mod/delete': {
+    input: ModDeleteInput;
+    output: ModDeleteOutput;
+  };
+  'mod/findRedundant': {
+    input: ModFindRedundantInput;
+    output: ModFindRedundantOutput;
+  };
+  'mod/install': {
+    input: ModInstallInput;
+    output: ModInstallOutput;
+  };
+  'mod/lint': {
+    input: ModLintInput;
+    output: ModLintOutput;
+  };
+  'mod/package': {
+    input: ModPackageInput;
+    output: ModPackageOutput;
+  };
+  'mod/readManifest': {
+    input: ModReadManifestInput;
+    output: ModReadManifestOutput;
+  };
+  'mod/readSelected': {
+    input: ModReadSelectedInput;
+    output: ModReadSelectedOutput;
+  };
+  'mod/rename': {
+    input: ModRenameInput;
+    output: ModRenameOutput;
+  };
+  'mod/updateManifest': {
+    input: ModUpdateManifestInput;
+    output: ModUpdateManifestOutput;
+  };
+  'mod/updateSelected': {
+    input: ModUpdateSelectedInput;
+    output: ModUpdateSelectedOutput;
+  };
+  'mod/updateSettings': {
+    input: ModUpdateSettingsInput;
+    output: ModUpdateSettingsOutput;
+  };
+  'mods/conflicts': {
+    input: ModsConflictsInput;
+    output: ModsConflictsOutput;
+  };
+  'mods/listAvailable': {
+    input: ModsListAvailableInput;
+    output: ModsListAvailableOutput;
+  };
+  'mods/listEditable': {
+    input: ModsListEditableInput;
+    output: ModsListEditableOutput;
+  };
+  'resources/list': {
+    input: ResourcesListInput;
+    output: ResourcesListOutput;
+  };
+  'sound/read': {
+    input: SoundReadInput;
+    output: SoundReadOutput;
+  };
+  'text/persist': {
+    input: TextPersistInput;
+    output: TextPersistOutput;
+  };
+  'text/read': {
+    input: TextReadInput;
+    output: TextReadOutput;
+  };
+  'toolset/listInvokables': {
+    input: ToolsetListInvokablesInput;
+    output: ToolsetListInvokablesOutput;
+  };
+  'toolset/readConfig': {
+    input: ToolsetReadConfigInput;
+    output: ToolsetReadConfigOutput;
+  };
+  'toolset/updateConfig': {
+    input: ToolsetUpdateConfigInput;
+    output: ToolsetUpdateConfigOutput;
+  };
+};
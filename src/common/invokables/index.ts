@@ -1,7 +1,12 @@
 import z from 'zod';
 import { dialogShowOpenDialogInvokableDefinition } from './dialogs';
+import {
+  edtPersistInvokableDefinition,
+  edtReadInvokableDefinition,
+} from './edts';
 import {
   toolsetCloseWindowInvokableDefinition,
+  toolsetListInvokablesInvokableDefinition,
   toolsetReadConfigInvokableDefinition,
   toolsetUpdateConfigInvokableDefinition,
 } from './toolset';
@@ -10,18 +15,51 @@ import {
   imageRenderInvokableDefinition,
 } from './images';
 import {
+  jsonBulkUpdateInvokableDefinition,
+  jsonCheckSchemaCompatibilityInvokableDefinition,
+  jsonDescribeInvokableDefinition,
+  jsonDiffInvokableDefinition,
+  jsonExportCsvInvokableDefinition,
+  jsonHistoryInvokableDefinition,
+  jsonImportCsvInvokableDefinition,
+  jsonListBackupsInvokableDefinition,
+  jsonListSchemasInvokableDefinition,
+  jsonMigrateInvokableDefinition,
   jsonPersistInvokableDefinition,
   jsonReadInvokableDefinition,
+  jsonRedoInvokableDefinition,
+  jsonRenameIdentifierInvokableDefinition,
+  jsonRenderSchemaReferenceInvokableDefinition,
+  jsonRestoreBackupInvokableDefinition,
+  jsonSearchInvokableDefinition,
+  jsonUndoInvokableDefinition,
 } from './jsons';
+import { l10nCoverageInvokableDefinition } from './l10n';
 import {
+  modCloneInvokableDefinition,
+  modConflictsInvokableDefinition,
   modCreateInvokableDefinition,
+  modDeleteInvokableDefinition,
+  modFindRedundantInvokableDefinition,
+  modInstallInvokableDefinition,
+  modLintInvokableDefinition,
   modListAvailableInvokableDefinition,
   modListEditableInvokableDefinition,
+  modPackageInvokableDefinition,
+  modReadManifestInvokableDefinition,
   modReadSelectedInvokableDefinition,
+  modRenameInvokableDefinition,
+  modUpdateManifestInvokableDefinition,
   modUpdateSelectedInvokableDefinition,
+  modUpdateSettingsInvokableDefinition,
 } from './mods';
 import { resourcesListInvokableDefinition } from './resources';
 import { soundReadInvokableDefinition } from './sounds';
+import {
+  textPersistInvokableDefinition,
+  textReadInvokableDefinition,
+} from './texts';
+import { RustInvokables } from './generated';
 
 export const INVOKE_CHANNEL = 'invoke';
 
@@ -54,24 +92,60 @@ export type InvokableOutput<D> =
 const ALL_INVOKABLES = [
   dialogShowOpenDialogInvokableDefinition,
 
+  edtReadInvokableDefinition,
+  edtPersistInvokableDefinition,
+
   imageReadMetadataInvokableDefinition,
   imageRenderInvokableDefinition,
 
   jsonReadInvokableDefinition,
   jsonPersistInvokableDefinition,
+  jsonListBackupsInvokableDefinition,
+  jsonRestoreBackupInvokableDefinition,
+  jsonSearchInvokableDefinition,
+  jsonRenameIdentifierInvokableDefinition,
+  jsonListSchemasInvokableDefinition,
+  jsonDiffInvokableDefinition,
+  jsonBulkUpdateInvokableDefinition,
+  jsonExportCsvInvokableDefinition,
+  jsonImportCsvInvokableDefinition,
+  jsonHistoryInvokableDefinition,
+  jsonUndoInvokableDefinition,
+  jsonRedoInvokableDefinition,
+  jsonCheckSchemaCompatibilityInvokableDefinition,
+  jsonMigrateInvokableDefinition,
+  jsonDescribeInvokableDefinition,
+  jsonRenderSchemaReferenceInvokableDefinition,
+
+  l10nCoverageInvokableDefinition,
 
   modListAvailableInvokableDefinition,
   modListEditableInvokableDefinition,
   modReadSelectedInvokableDefinition,
   modUpdateSelectedInvokableDefinition,
   modCreateInvokableDefinition,
+  modPackageInvokableDefinition,
+  modInstallInvokableDefinition,
+  modReadManifestInvokableDefinition,
+  modUpdateManifestInvokableDefinition,
+  modCloneInvokableDefinition,
+  modRenameInvokableDefinition,
+  modDeleteInvokableDefinition,
+  modUpdateSettingsInvokableDefinition,
+  modConflictsInvokableDefinition,
+  modLintInvokableDefinition,
+  modFindRedundantInvokableDefinition,
 
   resourcesListInvokableDefinition,
 
   soundReadInvokableDefinition,
 
+  textReadInvokableDefinition,
+  textPersistInvokableDefinition,
+
   toolsetReadConfigInvokableDefinition,
   toolsetUpdateConfigInvokableDefinition,
+  toolsetListInvokablesInvokableDefinition,
   toolsetCloseWindowInvokableDefinition,
 ];
 
@@ -91,3 +165,50 @@ export function getInvokableDefinitionByName<Name extends AnyInvokableName>(
     (invokable) => invokable.name === name,
   ) as InvokableFromName<Name>;
 }
+
+// Rust serializes `None` as `null` while the generated types mark such fields
+// optional, and untyped JSON values are narrowed by the definitions above
+type Serialized<T> = unknown extends T
+  ? any
+  : T extends (infer Item)[]
+    ? Serialized<Item>[]
+    : T extends object
+      ? {
+          [K in keyof T]-?: null extends T[K]
+            ? Serialized<Exclude<T[K], undefined>>
+            : Serialized<T[K]>;
+        }
+      : T;
+
+// Their outputs tie `partial` to which fields are set, which the Rust types
+// cannot express
+type NarrowedOutputInvokableName =
+  | 'toolset/readConfig'
+  | 'toolset/updateConfig';
+
+type RustInvokableName = AnyInvokableName & keyof RustInvokables;
+
+/**
+ * Invokables whose definitions above disagree with the generated Rust types
+ *
+ * Inputs passing validation have to be accepted by Rust and everything Rust
+ * returns has to pass validation.
+ */
+type MismatchedInvokableName = {
+  [Name in RustInvokableName]: [
+    InvokableInput<InvokableFromName<Name>>,
+  ] extends [RustInvokables[Name]['input']]
+    ? Name extends NarrowedOutputInvokableName
+      ? never
+      : [Serialized<RustInvokables[Name]['output']>] extends [
+            InvokableOutput<InvokableFromName<Name>>,
+          ]
+        ? never
+        : Name
+    : Name;
+}[RustInvokableName];
+
+type AssertNone<T extends never> = T;
+
+// Fails to compile with the names of the mismatched invokables
+export type CheckInvokablesMatchRust = AssertNone<MismatchedInvokableName>;
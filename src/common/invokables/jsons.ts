@@ -76,6 +76,7 @@ const READ_INPUT_SCHEMA = z.object({
 const OUTPUT_SCHEMA = z.object({
   schema: JSON_SCHEMA_SCHEMA,
   vanilla: JSON_ROOT_SCHEMA,
+  localized: z.optional(z.nullable(z.string())),
   value: z.nullable(JSON_ROOT_SCHEMA),
   patch: z.nullable(JSON_PATCH_SCHEMA),
 });
@@ -111,3 +112,510 @@ export const jsonPersistInvokableDefinition: JsonPersistInvokable = {
   inputSchema: PERSIST_INPUT_SCHEMA,
   outputSchema: OUTPUT_SCHEMA,
 };
+
+const LIST_BACKUPS_INPUT_SCHEMA = z.object({
+  file: z.string(),
+});
+
+const BACKUP_SCHEMA = z.object({
+  timestamp: z.number(),
+});
+
+export type JsonBackup = z.infer<typeof BACKUP_SCHEMA>;
+
+const LIST_BACKUPS_OUTPUT_SCHEMA = z.array(BACKUP_SCHEMA);
+
+export type JsonListBackupsInvokable = InvokableDefinition<
+  Category,
+  'listBackups',
+  z.infer<typeof LIST_BACKUPS_INPUT_SCHEMA>,
+  z.infer<typeof LIST_BACKUPS_OUTPUT_SCHEMA>
+>;
+
+export const jsonListBackupsInvokableDefinition: JsonListBackupsInvokable = {
+  name: 'json/listBackups',
+  inputSchema: LIST_BACKUPS_INPUT_SCHEMA,
+  outputSchema: LIST_BACKUPS_OUTPUT_SCHEMA,
+};
+
+const RESTORE_BACKUP_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  timestamp: z.number(),
+});
+
+export type JsonRestoreBackupInvokable = InvokableDefinition<
+  Category,
+  'restoreBackup',
+  z.infer<typeof RESTORE_BACKUP_INPUT_SCHEMA>,
+  z.infer<typeof OUTPUT_SCHEMA>
+>;
+
+export const jsonRestoreBackupInvokableDefinition: JsonRestoreBackupInvokable =
+  {
+    name: 'json/restoreBackup',
+    inputSchema: RESTORE_BACKUP_INPUT_SCHEMA,
+    outputSchema: OUTPUT_SCHEMA,
+  };
+
+export const JSON_PREDICATE_SCHEMA = z.union([
+  z.object({
+    op: z.literal('equals'),
+    field: z.string(),
+    value: z.any(),
+  }),
+  z.object({
+    op: z.literal('contains'),
+    field: z.string(),
+    value: z.any(),
+  }),
+  z.object({
+    op: z.literal('range'),
+    field: z.string(),
+    min: z.optional(z.nullable(z.number())),
+    max: z.optional(z.nullable(z.number())),
+  }),
+]);
+
+export type JsonPredicate = z.infer<typeof JSON_PREDICATE_SCHEMA>;
+
+const SEARCH_QUERY_SCHEMA = z.union([
+  z.object({
+    type: z.literal('text'),
+    text: z.string(),
+    caseSensitive: z.optional(z.nullable(z.boolean())),
+  }),
+  z.object({
+    type: z.literal('pointer'),
+    pattern: z.string(),
+  }),
+  z.object({
+    type: z.literal('predicate'),
+    predicate: JSON_PREDICATE_SCHEMA,
+  }),
+]);
+
+export type JsonSearchQuery = z.infer<typeof SEARCH_QUERY_SCHEMA>;
+
+const SEARCH_INPUT_SCHEMA = z.object({
+  query: SEARCH_QUERY_SCHEMA,
+});
+
+const SEARCH_HIT_SCHEMA = z.object({
+  file: z.string(),
+  pointer: z.string(),
+  itemName: z.nullable(z.string()),
+  value: z.any(),
+});
+
+export type JsonSearchHit = z.infer<typeof SEARCH_HIT_SCHEMA>;
+
+const SEARCH_OUTPUT_SCHEMA = z.array(SEARCH_HIT_SCHEMA);
+
+export type JsonSearchInvokable = InvokableDefinition<
+  Category,
+  'search',
+  z.infer<typeof SEARCH_INPUT_SCHEMA>,
+  z.infer<typeof SEARCH_OUTPUT_SCHEMA>
+>;
+
+export const jsonSearchInvokableDefinition: JsonSearchInvokable = {
+  name: 'json/search',
+  inputSchema: SEARCH_INPUT_SCHEMA,
+  outputSchema: SEARCH_OUTPUT_SCHEMA,
+};
+
+const RENAME_IDENTIFIER_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  from: z.string(),
+  to: z.string().min(1),
+  preview: z.optional(z.nullable(z.boolean())),
+});
+
+const IDENTIFIER_CHANGE_SCHEMA = z.object({
+  file: z.string(),
+  pointer: z.string(),
+  itemName: z.nullable(z.string()),
+  oldValue: z.any(),
+  newValue: z.any(),
+});
+
+export type JsonIdentifierChange = z.infer<typeof IDENTIFIER_CHANGE_SCHEMA>;
+
+const RENAME_IDENTIFIER_OUTPUT_SCHEMA = z.object({
+  changes: z.array(IDENTIFIER_CHANGE_SCHEMA),
+  applied: z.boolean(),
+});
+
+export type JsonRenameIdentifierInvokable = InvokableDefinition<
+  Category,
+  'renameIdentifier',
+  z.infer<typeof RENAME_IDENTIFIER_INPUT_SCHEMA>,
+  z.infer<typeof RENAME_IDENTIFIER_OUTPUT_SCHEMA>
+>;
+
+export const jsonRenameIdentifierInvokableDefinition: JsonRenameIdentifierInvokable =
+  {
+    name: 'json/renameIdentifier',
+    inputSchema: RENAME_IDENTIFIER_INPUT_SCHEMA,
+    outputSchema: RENAME_IDENTIFIER_OUTPUT_SCHEMA,
+  };
+
+const SCHEMA_ENTRY_SCHEMA = z.object({
+  file: z.string(),
+  title: z.nullable(z.string()),
+  description: z.nullable(z.string()),
+  hasValue: z.boolean(),
+  hasPatch: z.boolean(),
+});
+
+export type JsonSchemaEntry = z.infer<typeof SCHEMA_ENTRY_SCHEMA>;
+
+const LIST_SCHEMAS_OUTPUT_SCHEMA = z.array(SCHEMA_ENTRY_SCHEMA);
+
+export type JsonListSchemasInvokable = InvokableDefinition<
+  Category,
+  'listSchemas',
+  null,
+  z.infer<typeof LIST_SCHEMAS_OUTPUT_SCHEMA>
+>;
+
+export const jsonListSchemasInvokableDefinition: JsonListSchemasInvokable = {
+  name: 'json/listSchemas',
+  inputSchema: z.null(),
+  outputSchema: LIST_SCHEMAS_OUTPUT_SCHEMA,
+};
+
+const DIFF_INPUT_SCHEMA = z.object({
+  file: z.string(),
+});
+
+const DIFF_ITEM_SUMMARY_SCHEMA = z.object({
+  key: z.string(),
+  name: z.nullable(z.string()),
+  value: z.any(),
+});
+
+const DIFF_FIELD_SCHEMA = z.object({
+  field: z.string(),
+  oldValue: z.nullable(z.any()),
+  newValue: z.nullable(z.any()),
+});
+
+const DIFF_ITEM_SCHEMA = z.object({
+  key: z.string(),
+  name: z.nullable(z.string()),
+  fields: z.array(DIFF_FIELD_SCHEMA),
+});
+
+const DIFF_OUTPUT_SCHEMA = z.object({
+  file: z.string(),
+  keyProperty: z.nullable(z.string()),
+  added: z.array(DIFF_ITEM_SUMMARY_SCHEMA),
+  removed: z.array(DIFF_ITEM_SUMMARY_SCHEMA),
+  changed: z.array(DIFF_ITEM_SCHEMA),
+  markdown: z.string(),
+});
+
+export type JsonDiff = z.infer<typeof DIFF_OUTPUT_SCHEMA>;
+
+export type JsonDiffInvokable = InvokableDefinition<
+  Category,
+  'diff',
+  z.infer<typeof DIFF_INPUT_SCHEMA>,
+  JsonDiff
+>;
+
+export const jsonDiffInvokableDefinition: JsonDiffInvokable = {
+  name: 'json/diff',
+  inputSchema: DIFF_INPUT_SCHEMA,
+  outputSchema: DIFF_OUTPUT_SCHEMA,
+};
+
+export const SAVE_MODE_SCHEMA = z.union([
+  z.literal('patch'),
+  z.literal('replace'),
+]);
+
+const ASSIGNMENT_SCHEMA = z.union([
+  z.object({
+    op: z.literal('set'),
+    field: z.string(),
+    value: z.any(),
+  }),
+  z.object({
+    op: z.literal('add'),
+    field: z.string(),
+    value: z.number(),
+  }),
+  z.object({
+    op: z.literal('multiply'),
+    field: z.string(),
+    value: z.number(),
+  }),
+]);
+
+export type JsonAssignment = z.infer<typeof ASSIGNMENT_SCHEMA>;
+
+const BULK_UPDATE_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  filter: z.optional(z.nullable(JSON_PREDICATE_SCHEMA)),
+  assignments: z.array(ASSIGNMENT_SCHEMA).min(1),
+  saveMode: z.optional(z.nullable(SAVE_MODE_SCHEMA)),
+  dryRun: z.optional(z.nullable(z.boolean())),
+});
+
+const BULK_CHANGE_SCHEMA = z.object({
+  pointer: z.string(),
+  itemName: z.nullable(z.string()),
+  field: z.string(),
+  oldValue: z.nullable(z.any()),
+  newValue: z.any(),
+});
+
+const BULK_UPDATE_OUTPUT_SCHEMA = z.object({
+  changes: z.array(BULK_CHANGE_SCHEMA),
+  file: z.nullable(OUTPUT_SCHEMA),
+});
+
+export type JsonBulkUpdateInvokable = InvokableDefinition<
+  Category,
+  'bulkUpdate',
+  z.infer<typeof BULK_UPDATE_INPUT_SCHEMA>,
+  z.infer<typeof BULK_UPDATE_OUTPUT_SCHEMA>
+>;
+
+export const jsonBulkUpdateInvokableDefinition: JsonBulkUpdateInvokable = {
+  name: 'json/bulkUpdate',
+  inputSchema: BULK_UPDATE_INPUT_SCHEMA,
+  outputSchema: BULK_UPDATE_OUTPUT_SCHEMA,
+};
+
+const EXPORT_CSV_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  path: z.string(),
+});
+
+const EXPORT_CSV_OUTPUT_SCHEMA = z.object({
+  columns: z.array(z.string()),
+  rows: z.number(),
+});
+
+export type JsonExportCsvInvokable = InvokableDefinition<
+  Category,
+  'exportCsv',
+  z.infer<typeof EXPORT_CSV_INPUT_SCHEMA>,
+  z.infer<typeof EXPORT_CSV_OUTPUT_SCHEMA>
+>;
+
+export const jsonExportCsvInvokableDefinition: JsonExportCsvInvokable = {
+  name: 'json/exportCsv',
+  inputSchema: EXPORT_CSV_INPUT_SCHEMA,
+  outputSchema: EXPORT_CSV_OUTPUT_SCHEMA,
+};
+
+const IMPORT_CSV_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  path: z.string(),
+  saveMode: z.optional(z.nullable(SAVE_MODE_SCHEMA)),
+});
+
+const CSV_CELL_ERROR_SCHEMA = z.object({
+  row: z.number(),
+  column: z.string(),
+  value: z.string(),
+  message: z.string(),
+});
+
+export type JsonCsvCellError = z.infer<typeof CSV_CELL_ERROR_SCHEMA>;
+
+const IMPORT_CSV_OUTPUT_SCHEMA = z.object({
+  errors: z.array(CSV_CELL_ERROR_SCHEMA),
+  file: z.nullable(OUTPUT_SCHEMA),
+});
+
+export type JsonImportCsvInvokable = InvokableDefinition<
+  Category,
+  'importCsv',
+  z.infer<typeof IMPORT_CSV_INPUT_SCHEMA>,
+  z.infer<typeof IMPORT_CSV_OUTPUT_SCHEMA>
+>;
+
+export const jsonImportCsvInvokableDefinition: JsonImportCsvInvokable = {
+  name: 'json/importCsv',
+  inputSchema: IMPORT_CSV_INPUT_SCHEMA,
+  outputSchema: IMPORT_CSV_OUTPUT_SCHEMA,
+};
+
+const HISTORY_OUTPUT_SCHEMA = z.object({
+  entries: z.array(
+    z.object({
+      timestamp: z.number(),
+      files: z.array(z.string()),
+      undone: z.boolean(),
+    }),
+  ),
+  canUndo: z.boolean(),
+  canRedo: z.boolean(),
+});
+
+export type JsonHistoryInvokable = InvokableDefinition<
+  Category,
+  'history',
+  null,
+  z.infer<typeof HISTORY_OUTPUT_SCHEMA>
+>;
+
+export const jsonHistoryInvokableDefinition: JsonHistoryInvokable = {
+  name: 'json/history',
+  inputSchema: z.null(),
+  outputSchema: HISTORY_OUTPUT_SCHEMA,
+};
+
+const JOURNAL_STEP_SCHEMA = z.object({
+  timestamp: z.number(),
+  files: z.array(
+    z.object({
+      file: z.string(),
+      json: OUTPUT_SCHEMA,
+    }),
+  ),
+});
+
+export type JsonUndoInvokable = InvokableDefinition<
+  Category,
+  'undo',
+  null,
+  z.infer<typeof JOURNAL_STEP_SCHEMA>
+>;
+
+export const jsonUndoInvokableDefinition: JsonUndoInvokable = {
+  name: 'json/undo',
+  inputSchema: z.null(),
+  outputSchema: JOURNAL_STEP_SCHEMA,
+};
+
+export type JsonRedoInvokable = InvokableDefinition<
+  Category,
+  'redo',
+  null,
+  z.infer<typeof JOURNAL_STEP_SCHEMA>
+>;
+
+export const jsonRedoInvokableDefinition: JsonRedoInvokable = {
+  name: 'json/redo',
+  inputSchema: z.null(),
+  outputSchema: JOURNAL_STEP_SCHEMA,
+};
+
+const PENDING_MIGRATION_SCHEMA = z.object({
+  id: z.string(),
+  description: z.string(),
+  files: z.array(z.string()),
+});
+
+const COMPATIBILITY_REPORT_SCHEMA = z.object({
+  files: z.array(
+    z.object({
+      file: z.string(),
+      issues: z.array(
+        z.object({
+          source: z.union([
+            z.literal('file'),
+            z.literal('value'),
+            z.literal('patch'),
+          ]),
+          pointer: z.string(),
+          message: z.string(),
+        }),
+      ),
+    }),
+  ),
+  pendingMigrations: z.array(PENDING_MIGRATION_SCHEMA),
+});
+
+export type JsonCheckSchemaCompatibilityInvokable = InvokableDefinition<
+  Category,
+  'checkSchemaCompatibility',
+  null,
+  z.infer<typeof COMPATIBILITY_REPORT_SCHEMA>
+>;
+
+export const jsonCheckSchemaCompatibilityInvokableDefinition: JsonCheckSchemaCompatibilityInvokable =
+  {
+    name: 'json/checkSchemaCompatibility',
+    inputSchema: z.null(),
+    outputSchema: COMPATIBILITY_REPORT_SCHEMA,
+  };
+
+const MIGRATE_OUTPUT_SCHEMA = z.object({
+  applied: z.array(PENDING_MIGRATION_SCHEMA),
+});
+
+export type JsonMigrateInvokable = InvokableDefinition<
+  Category,
+  'migrate',
+  null,
+  z.infer<typeof MIGRATE_OUTPUT_SCHEMA>
+>;
+
+export const jsonMigrateInvokableDefinition: JsonMigrateInvokable = {
+  name: 'json/migrate',
+  inputSchema: z.null(),
+  outputSchema: MIGRATE_OUTPUT_SCHEMA,
+};
+
+const DESCRIBE_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  pointer: z.string(),
+});
+
+const DESCRIBE_OUTPUT_SCHEMA = z.object({
+  title: z.nullable(z.string()),
+  description: z.nullable(z.string()),
+  types: z.array(z.string()),
+  enum: z.nullable(z.array(z.any())),
+  default: z.nullable(z.any()),
+  minimum: z.nullable(z.number()),
+  maximum: z.nullable(z.number()),
+  exclusiveMinimum: z.boolean(),
+  exclusiveMaximum: z.boolean(),
+});
+
+export type JsonFieldDescription = z.infer<typeof DESCRIBE_OUTPUT_SCHEMA>;
+
+export type JsonDescribeInvokable = InvokableDefinition<
+  Category,
+  'describe',
+  z.infer<typeof DESCRIBE_INPUT_SCHEMA>,
+  z.infer<typeof DESCRIBE_OUTPUT_SCHEMA>
+>;
+
+export const jsonDescribeInvokableDefinition: JsonDescribeInvokable = {
+  name: 'json/describe',
+  inputSchema: DESCRIBE_INPUT_SCHEMA,
+  outputSchema: DESCRIBE_OUTPUT_SCHEMA,
+};
+
+const RENDER_SCHEMA_REFERENCE_INPUT_SCHEMA = z.object({
+  dir: z.string(),
+  format: z.union([z.literal('markdown'), z.literal('html')]),
+});
+
+const RENDER_SCHEMA_REFERENCE_OUTPUT_SCHEMA = z.object({
+  index: z.string(),
+  pages: z.number(),
+});
+
+export type JsonRenderSchemaReferenceInvokable = InvokableDefinition<
+  Category,
+  'renderSchemaReference',
+  z.infer<typeof RENDER_SCHEMA_REFERENCE_INPUT_SCHEMA>,
+  z.infer<typeof RENDER_SCHEMA_REFERENCE_OUTPUT_SCHEMA>
+>;
+
+export const jsonRenderSchemaReferenceInvokableDefinition: JsonRenderSchemaReferenceInvokable =
+  {
+    name: 'json/renderSchemaReference',
+    inputSchema: RENDER_SCHEMA_REFERENCE_INPUT_SCHEMA,
+    outputSchema: RENDER_SCHEMA_REFERENCE_OUTPUT_SCHEMA,
+  };
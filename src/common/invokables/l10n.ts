@@ -0,0 +1,43 @@
+import z from 'zod';
+import { InvokableDefinition } from '.';
+
+type Category = 'l10n';
+
+export const LANGUAGE_SCHEMA = z.enum([
+  'english',
+  'dutch',
+  'french',
+  'german',
+  'italian',
+  'polish',
+  'russian',
+  'chinese',
+]);
+
+export type Language = z.infer<typeof LANGUAGE_SCHEMA>;
+
+const COVERAGE_OUTPUT_SCHEMA = z.array(
+  z.object({
+    language: LANGUAGE_SCHEMA,
+    resources: z.array(
+      z.object({
+        file: z.string(),
+        resource: z.string(),
+      }),
+    ),
+    missing: z.array(z.string()),
+  }),
+);
+
+export type L10nCoverageInvokable = InvokableDefinition<
+  Category,
+  'coverage',
+  null,
+  z.infer<typeof COVERAGE_OUTPUT_SCHEMA>
+>;
+
+export const l10nCoverageInvokableDefinition: L10nCoverageInvokable = {
+  name: 'l10n/coverage',
+  inputSchema: z.null(),
+  outputSchema: COVERAGE_OUTPUT_SCHEMA,
+};
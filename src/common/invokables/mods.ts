@@ -31,8 +31,20 @@ export const MOD_SCHEMA = z.object({
 
 export type Mod = z.infer<typeof MOD_SCHEMA>;
 
+const MOD_SETTINGS_SCHEMA = z.object({
+  required_mods: z.array(z.string().min(1)).meta({
+    title: 'Required Mods',
+    description:
+      'Mods that are loaded beneath your mod while editing it. Mods listed first take precedence.',
+  }),
+});
+
+export type ModSettings = z.infer<typeof MOD_SETTINGS_SCHEMA>;
+
 const EDITABLE_MOD_SCHEMA = MOD_SCHEMA.extend({
   path: z.string(),
+  settings: z.optional(z.nullable(MOD_SETTINGS_SCHEMA)),
+  settingsError: z.optional(z.nullable(z.string())),
 });
 
 export type EditableMod = z.infer<typeof EDITABLE_MOD_SCHEMA>;
@@ -108,3 +120,253 @@ export const modCreateInvokableDefinition: ModCreateInvokable = {
   inputSchema: MOD_SCHEMA,
   outputSchema: EDITABLE_MOD_SCHEMA,
 };
+
+const PACKAGE_INPUT_SCHEMA = z.object({
+  path: z.string(),
+  extras: z.optional(z.nullable(z.array(z.string()))),
+});
+
+const PACKAGE_OUTPUT_SCHEMA = z.object({
+  path: z.string(),
+  checksumPath: z.string(),
+  sha256: z.string(),
+  files: z.number(),
+});
+
+export type ModPackageInvokable = InvokableDefinition<
+  Category,
+  'package',
+  z.infer<typeof PACKAGE_INPUT_SCHEMA>,
+  z.infer<typeof PACKAGE_OUTPUT_SCHEMA>
+>;
+
+export const modPackageInvokableDefinition: ModPackageInvokable = {
+  name: 'mod/package',
+  inputSchema: PACKAGE_INPUT_SCHEMA,
+  outputSchema: PACKAGE_OUTPUT_SCHEMA,
+};
+
+const INSTALL_INPUT_SCHEMA = z.object({
+  path: z.string(),
+});
+
+export type ModInstallInvokable = InvokableDefinition<
+  Category,
+  'install',
+  z.infer<typeof INSTALL_INPUT_SCHEMA>,
+  z.infer<typeof EDITABLE_MOD_SCHEMA>
+>;
+
+export const modInstallInvokableDefinition: ModInstallInvokable = {
+  name: 'mod/install',
+  inputSchema: INSTALL_INPUT_SCHEMA,
+  outputSchema: EDITABLE_MOD_SCHEMA,
+};
+
+const MANIFEST_SCHEMA = z.looseObject({
+  name: z.string(),
+  version: z.string(),
+  description: z.optional(z.nullable(z.string())),
+});
+
+export type ModReadManifestInvokable = InvokableDefinition<
+  Category,
+  'readManifest',
+  null,
+  z.infer<typeof MANIFEST_SCHEMA>
+>;
+
+export const modReadManifestInvokableDefinition: ModReadManifestInvokable = {
+  name: 'mod/readManifest',
+  inputSchema: z.null(),
+  outputSchema: MANIFEST_SCHEMA,
+};
+
+const UPDATE_MANIFEST_INPUT_SCHEMA = MOD_SCHEMA.omit({ id: true }).extend({
+  fields: z.optional(z.nullable(z.record(z.string(), z.unknown()))),
+});
+
+export type ModUpdateManifestInvokable = InvokableDefinition<
+  Category,
+  'updateManifest',
+  z.infer<typeof UPDATE_MANIFEST_INPUT_SCHEMA>,
+  z.infer<typeof EDITABLE_MOD_SCHEMA>
+>;
+
+export const modUpdateManifestInvokableDefinition: ModUpdateManifestInvokable =
+  {
+    name: 'mod/updateManifest',
+    inputSchema: UPDATE_MANIFEST_INPUT_SCHEMA,
+    outputSchema: EDITABLE_MOD_SCHEMA,
+  };
+
+const CLONE_INPUT_SCHEMA = z.object({
+  id: z.string(),
+  newId: MOD_SCHEMA.shape.id,
+  name: z.optional(z.nullable(z.string().min(1))),
+});
+
+export type ModCloneInvokable = InvokableDefinition<
+  Category,
+  'clone',
+  z.infer<typeof CLONE_INPUT_SCHEMA>,
+  z.infer<typeof EDITABLE_MOD_SCHEMA>
+>;
+
+export const modCloneInvokableDefinition: ModCloneInvokable = {
+  name: 'mod/clone',
+  inputSchema: CLONE_INPUT_SCHEMA,
+  outputSchema: EDITABLE_MOD_SCHEMA,
+};
+
+const RENAME_INPUT_SCHEMA = z.object({
+  id: z.string(),
+  newId: MOD_SCHEMA.shape.id,
+});
+
+export type ModRenameInvokable = InvokableDefinition<
+  Category,
+  'rename',
+  z.infer<typeof RENAME_INPUT_SCHEMA>,
+  z.infer<typeof EDITABLE_MOD_SCHEMA>
+>;
+
+export const modRenameInvokableDefinition: ModRenameInvokable = {
+  name: 'mod/rename',
+  inputSchema: RENAME_INPUT_SCHEMA,
+  outputSchema: EDITABLE_MOD_SCHEMA,
+};
+
+const DELETE_INPUT_SCHEMA = z.object({
+  id: z.string(),
+});
+
+const DELETE_OUTPUT_SCHEMA = z.object({
+  trashPath: z.string(),
+});
+
+export type ModDeleteInvokable = InvokableDefinition<
+  Category,
+  'delete',
+  z.infer<typeof DELETE_INPUT_SCHEMA>,
+  z.infer<typeof DELETE_OUTPUT_SCHEMA>
+>;
+
+export const modDeleteInvokableDefinition: ModDeleteInvokable = {
+  name: 'mod/delete',
+  inputSchema: DELETE_INPUT_SCHEMA,
+  outputSchema: DELETE_OUTPUT_SCHEMA,
+};
+
+const UPDATE_SETTINGS_INPUT_SCHEMA = MOD_SETTINGS_SCHEMA.extend({
+  id: z.optional(z.nullable(z.string())),
+});
+
+export type ModUpdateSettingsInvokable = InvokableDefinition<
+  Category,
+  'updateSettings',
+  z.infer<typeof UPDATE_SETTINGS_INPUT_SCHEMA>,
+  z.infer<typeof MOD_SETTINGS_SCHEMA>
+>;
+
+export const modUpdateSettingsInvokableDefinition: ModUpdateSettingsInvokable =
+  {
+    name: 'mod/updateSettings',
+    inputSchema: UPDATE_SETTINGS_INPUT_SCHEMA,
+    outputSchema: MOD_SETTINGS_SCHEMA,
+  };
+
+const CONFLICTS_INPUT_SCHEMA = z.object({
+  ids: z.array(z.string()).min(2),
+});
+
+const CONFLICTS_OUTPUT_SCHEMA = z.array(
+  z.object({
+    resource: z.string(),
+    mods: z.array(z.string()),
+    json: z.nullable(
+      z.object({
+        kind: z.enum([
+          'identical',
+          'differentItems',
+          'differentFields',
+          'sameFields',
+        ]),
+        fields: z.array(
+          z.object({
+            key: z.string(),
+            field: z.string(),
+            mods: z.array(z.string()),
+          }),
+        ),
+      }),
+    ),
+    error: z.nullable(z.string()),
+  }),
+);
+
+export type ModConflictsInvokable = InvokableDefinition<
+  CategoryPlural,
+  'conflicts',
+  z.infer<typeof CONFLICTS_INPUT_SCHEMA>,
+  z.infer<typeof CONFLICTS_OUTPUT_SCHEMA>
+>;
+
+export const modConflictsInvokableDefinition: ModConflictsInvokable = {
+  name: 'mods/conflicts',
+  inputSchema: CONFLICTS_INPUT_SCHEMA,
+  outputSchema: CONFLICTS_OUTPUT_SCHEMA,
+};
+
+const LINT_OUTPUT_SCHEMA = z.array(
+  z.object({
+    severity: z.enum(['error', 'warning', 'info']),
+    check: z.enum([
+      'manifest',
+      'unknownFile',
+      'caseCollision',
+      'schema',
+      'patch',
+      'emptyDirectory',
+      'identicalToVanilla',
+    ]),
+    file: z.nullable(z.string()),
+    pointer: z.nullable(z.string()),
+    message: z.string(),
+  }),
+);
+
+export type ModLintInvokable = InvokableDefinition<
+  Category,
+  'lint',
+  null,
+  z.infer<typeof LINT_OUTPUT_SCHEMA>
+>;
+
+export const modLintInvokableDefinition: ModLintInvokable = {
+  name: 'mod/lint',
+  inputSchema: z.null(),
+  outputSchema: LINT_OUTPUT_SCHEMA,
+};
+
+const FIND_REDUNDANT_INPUT_SCHEMA = z.object({
+  delete: z.boolean(),
+});
+
+const FIND_REDUNDANT_OUTPUT_SCHEMA = z.object({
+  files: z.array(z.string()),
+  trashPath: z.nullable(z.string()),
+});
+
+export type ModFindRedundantInvokable = InvokableDefinition<
+  Category,
+  'findRedundant',
+  z.infer<typeof FIND_REDUNDANT_INPUT_SCHEMA>,
+  z.infer<typeof FIND_REDUNDANT_OUTPUT_SCHEMA>
+>;
+
+export const modFindRedundantInvokableDefinition: ModFindRedundantInvokable = {
+  name: 'mod/findRedundant',
+  inputSchema: FIND_REDUNDANT_INPUT_SCHEMA,
+  outputSchema: FIND_REDUNDANT_OUTPUT_SCHEMA,
+};
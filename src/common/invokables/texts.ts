@@ -0,0 +1,59 @@
+import z from 'zod';
+import { InvokableDefinition } from '.';
+
+type Category = 'text';
+
+export const TEXT_ENCODING_SCHEMA = z.union([
+  z.literal('utf8'),
+  z.literal('utf8Bom'),
+  z.literal('utf16Le'),
+  z.literal('utf16Be'),
+  z.literal('windows1252'),
+]);
+
+export type TextEncoding = z.infer<typeof TEXT_ENCODING_SCHEMA>;
+
+const TEXT_FILE_SCHEMA = z.object({
+  content: z.string(),
+  encoding: TEXT_ENCODING_SCHEMA,
+  modified: z.boolean(),
+  localized: z.nullable(z.string()),
+});
+
+const READ_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  expectedEncoding: z.optional(z.nullable(TEXT_ENCODING_SCHEMA)),
+});
+
+export type TextReadInvokable = InvokableDefinition<
+  Category,
+  'read',
+  z.infer<typeof READ_INPUT_SCHEMA>,
+  z.infer<typeof TEXT_FILE_SCHEMA>
+>;
+
+export const textReadInvokableDefinition: TextReadInvokable = {
+  name: 'text/read',
+  inputSchema: READ_INPUT_SCHEMA,
+  outputSchema: TEXT_FILE_SCHEMA,
+};
+
+const PERSIST_INPUT_SCHEMA = z.object({
+  file: z.string(),
+  content: z.string(),
+  encoding: z.optional(z.nullable(TEXT_ENCODING_SCHEMA)),
+  expectedEncoding: z.optional(z.nullable(TEXT_ENCODING_SCHEMA)),
+});
+
+export type TextPersistInvokable = InvokableDefinition<
+  Category,
+  'persist',
+  z.infer<typeof PERSIST_INPUT_SCHEMA>,
+  z.infer<typeof TEXT_FILE_SCHEMA>
+>;
+
+export const textPersistInvokableDefinition: TextPersistInvokable = {
+  name: 'text/persist',
+  inputSchema: PERSIST_INPUT_SCHEMA,
+  outputSchema: TEXT_FILE_SCHEMA,
+};
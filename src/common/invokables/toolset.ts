@@ -1,11 +1,14 @@
 import z from 'zod';
 import { InvokableDefinition } from '.';
+import { ToolsetListInvokablesOutput } from './generated';
+import { LANGUAGE_SCHEMA } from './l10n';
 
 const PARTIAL_TOOLSET_CONFIG_SCHEMA = z.object({
   stracciatellaHome: z.nullable(z.string()),
   vanillaGameDir: z.nullable(z.string()),
   stracciatellaInstallDir: z.nullable(z.string()),
   lastSelectedMod: z.nullable(z.string()),
+  language: z.nullable(LANGUAGE_SCHEMA),
 });
 
 export type PartialToolsetConfig = z.infer<
@@ -32,6 +35,11 @@ export const FULL_TOOLSET_CONFIG_SCHEMA = z.object({
     title: 'Last Selected Mod',
     description: 'The last mod that was selected in the mod selection dialog.',
   }),
+  language: z.nullable(LANGUAGE_SCHEMA).meta({
+    title: 'Language',
+    description:
+      'Language specific variants of files and folders for this language are used when reading resources. Leave empty to always use the default files.',
+  }),
 });
 
 export type FullToolsetConfig = z.infer<typeof FULL_TOOLSET_CONFIG_SCHEMA>;
@@ -79,6 +87,26 @@ export const toolsetUpdateConfigInvokableDefinition: ToolsetUpdateConfigInvokabl
     outputSchema: TOOLSET_CONFIG_SCHEMA,
   };
 
+export type ToolsetListInvokablesInvokable = InvokableDefinition<
+  Category,
+  'listInvokables',
+  null,
+  ToolsetListInvokablesOutput
+>;
+
+export const toolsetListInvokablesInvokableDefinition: ToolsetListInvokablesInvokable =
+  {
+    name: 'toolset/listInvokables',
+    inputSchema: z.null(),
+    outputSchema: z.array(
+      z.object({
+        name: z.string(),
+        inputSchema: z.unknown(),
+        outputSchema: z.unknown(),
+      }),
+    ),
+  };
+
 export type ToolsetCloseWindowInvokable = InvokableDefinition<
   Category,
   'closeWindow',
@@ -5,6 +5,10 @@ import {
   InvokableName,
   InvokableOutput,
 } from '../../common/invokables';
+import {
+  INVOKE_ERROR_SCHEMA,
+  InvokeResponse,
+} from '../../common/invokables/errors';
 import { dialogShowOpenDialogMainJSInvokable } from './dialogs';
 import { toolsetCloseWindowMainJsInvokable } from './toolset';
 import { IpcMainInvokeEvent } from 'electron';
@@ -32,7 +36,7 @@ export async function handleInvoke(
   state: ReturnType<typeof rustInterface.newAppState>,
   event: IpcMainInvokeEvent,
   payload: unknown,
-) {
+): Promise<InvokeResponse> {
   const { name, input } = await invokeSchema.parseAsync(payload);
 
   // First try JS invokables
@@ -40,13 +44,25 @@ export async function handleInvoke(
   if (invokable) {
     const p = await invokable.inputSchema.parseAsync(input);
     const result = await invokable.func(p);
-    return result;
+    return { result };
   }
 
   // Otherwise try Rust invokables
-  const result = await rustInterface.invoke(
-    state,
-    JSON.stringify({ func: name, params: input }),
-  );
-  return JSON.parse(result);
+  try {
+    const result = await rustInterface.invoke(
+      state,
+      JSON.stringify({ func: name, params: input }),
+    );
+    return { result: JSON.parse(result) };
+  } catch (error) {
+    // Thrown errors lose their properties over IPC, so structured errors are returned instead
+    if (
+      error instanceof Error &&
+      'details' in error &&
+      typeof error.details === 'string'
+    ) {
+      return { error: INVOKE_ERROR_SCHEMA.parse(JSON.parse(error.details)) };
+    }
+    throw error;
+  }
 }
@@ -4,6 +4,7 @@ import rustInterface from '../../src-rust/target/stracciatella-toolset.node';
 interface RustInterface {
   initLogger: () => void;
   newAppState: () => symbol;
+  // Rejects with an `Error` whose `details` are the JSON of a `SerializedInvokeError`
   invoke: (config: symbol, payload: string) => Promise<string>;
 }
 
@@ -38,6 +38,7 @@ function Configure() {
     vanillaGameDir: '',
     stracciatellaInstallDir: '',
     lastSelectedMod: null,
+    language: null,
   });
   const [valid, setValid] = useState(false);
   const change = useCallback((ev: IChangeEvent<PartialToolsetConfig>) => {
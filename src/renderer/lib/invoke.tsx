@@ -5,6 +5,10 @@ import {
   InvokableInput,
   InvokableOutput,
 } from '../../common/invokables';
+import {
+  INVOKE_RESPONSE_SCHEMA,
+  InvokeError,
+} from '../../common/invokables/errors';
 import { z } from 'zod';
 
 export async function invoke<Name extends AnyInvokableName>(
@@ -13,9 +17,13 @@ export async function invoke<Name extends AnyInvokableName>(
 ): Promise<InvokableOutput<InvokableFromName<Name>>> {
   try {
     const res = await window.electronAPI.invoke({ name, input });
+    const response = await INVOKE_RESPONSE_SCHEMA.parseAsync(res);
+    if ('error' in response) {
+      throw new InvokeError(response.error);
+    }
     const definition = getInvokableDefinitionByName(name);
     const schema = definition.outputSchema;
-    const output = await schema.parseAsync(res);
+    const output = await schema.parseAsync(response.result);
     return output as InvokableOutput<InvokableFromName<Name>>;
   } catch (error) {
     if (error instanceof z.ZodError) {